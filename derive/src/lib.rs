@@ -0,0 +1,120 @@
+//! Derive macros for the algebraic traits of the `category` crate. Use them through the `derive`
+//! feature of `category` rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Generics, Index};
+
+/// Derives `Semigroup` for a struct by combining each field with its own `op`, in declaration
+/// order. Every type parameter of the struct is required to be a `Semigroup` too.
+#[proc_macro_derive(Semigroup)]
+pub fn derive_semigroup(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_semigroup(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_semigroup(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = struct_fields(input, "Semigroup")?;
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(::category::traits::Semigroup));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = construct(fields, |member, span| {
+        quote_spanned! {span=>
+            ::category::traits::Semigroup::op(self.#member, other.#member)
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::category::traits::Semigroup for #name #ty_generics #where_clause {
+            fn op(self, other: Self) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+/// Derives `Monoid` for a struct, where `empty` is built from the `empty` of each field. The
+/// struct needs to implement `Semigroup` as well, for instance by deriving it.
+#[proc_macro_derive(Monoid)]
+pub fn derive_monoid(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_monoid(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_monoid(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = struct_fields(input, "Monoid")?;
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(::category::traits::Monoid));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = construct(fields, |_, span| {
+        quote_spanned! {span=>
+            ::category::traits::Monoid::empty()
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::category::traits::Monoid for #name #ty_generics #where_clause {
+            fn empty() -> Self {
+                #body
+            }
+        }
+    })
+}
+
+/// Returns the fields of a struct, or an error naming the derived trait for enums and unions.
+fn struct_fields<'a>(input: &'a DeriveInput, tr: &str) -> syn::Result<&'a Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        Data::Enum(data) => Err(syn::Error::new(
+            data.enum_token.span(),
+            format!("`{}` can only be derived for structs, not enums", tr),
+        )),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span(),
+            format!("`{}` can only be derived for structs, not unions", tr),
+        )),
+    }
+}
+
+/// Bounds every type parameter by `bound`.
+fn add_bounds(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+/// Builds `Self` from an expression for each field, which is given the field's member (its name
+/// or index) and the span of its type to report errors against.
+fn construct(
+    fields: &Fields,
+    field_expr: impl Fn(TokenStream2, proc_macro2::Span) -> TokenStream2,
+) -> TokenStream2 {
+    match fields {
+        Fields::Named(named) => {
+            let inits = named.named.iter().map(|f| {
+                let ident = f.ident.as_ref().unwrap();
+                let expr = field_expr(quote!(#ident), f.ty.span());
+                quote!(#ident: #expr)
+            });
+            quote!(Self { #(#inits),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let inits = unnamed.unnamed.iter().enumerate().map(|(i, f)| {
+                let index = Index::from(i);
+                field_expr(quote!(#index), f.ty.span())
+            });
+            quote!(Self(#(#inits),*))
+        }
+        Fields::Unit => quote!(Self),
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/semigroup_struct.rs");
+    t.compile_fail("tests/ui/semigroup_enum.rs");
+    t.compile_fail("tests/ui/semigroup_missing_field_impl.rs");
+    t.pass("tests/ui/monoid_struct.rs");
+    t.compile_fail("tests/ui/monoid_missing_field_impl.rs");
+}
@@ -0,0 +1,10 @@
+use category::traits::{Monoid, Semigroup};
+use category::{MinBy, Sum};
+
+#[derive(Semigroup, Monoid)]
+struct Stats {
+    total: Sum<u64>,
+    first: MinBy<String, u64>,
+}
+
+fn main() {}
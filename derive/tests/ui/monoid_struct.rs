@@ -0,0 +1,36 @@
+use category::traits::{Monoid, Semigroup};
+use category::{Count, Max, Sum};
+
+#[derive(Clone, Debug, PartialEq, Semigroup, Monoid)]
+struct Stats {
+    total: Sum<u64>,
+    peak: Max<u64>,
+    count: Count,
+    names: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Semigroup, Monoid)]
+struct Pair<A, B>(A, B);
+
+fn main() {
+    let x = Stats {
+        total: Sum(3),
+        peak: Max(3),
+        count: Count(1),
+        names: vec![String::from("a")],
+    };
+    assert_eq!(
+        Stats::empty(),
+        Stats {
+            total: Sum(0),
+            peak: Max(0),
+            count: Count(0),
+            names: vec![],
+        }
+    );
+    assert_eq!(Stats::empty().op(x.clone()), x);
+    assert_eq!(x.clone().op(Stats::empty()), x);
+
+    let p = Pair(Sum(2), String::from("b"));
+    assert_eq!(Pair::empty().op(p), Pair(Sum(2), String::from("b")));
+}
@@ -0,0 +1,9 @@
+use category::traits::Semigroup;
+
+#[derive(Semigroup)]
+enum Choice {
+    Left(Vec<u8>),
+    Right(String),
+}
+
+fn main() {}
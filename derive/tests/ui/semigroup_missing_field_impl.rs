@@ -0,0 +1,12 @@
+use category::traits::Semigroup;
+use category::Sum;
+
+struct Opaque;
+
+#[derive(Semigroup)]
+struct Stats {
+    total: Sum<u64>,
+    opaque: Opaque,
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use category::traits::Semigroup;
+use category::{Count, Max, Sum};
+
+#[derive(Debug, PartialEq, Semigroup)]
+struct Stats {
+    total: Sum<u64>,
+    peak: Max<u64>,
+    count: Count,
+}
+
+#[derive(Debug, PartialEq, Semigroup)]
+struct Pair<A, B>(A, B);
+
+#[derive(Debug, PartialEq, Semigroup)]
+struct Marker;
+
+fn main() {
+    let stats = |x| Stats {
+        total: Sum(x),
+        peak: Max(x),
+        count: Count(1),
+    };
+    let r = stats(3).op(stats(7)).op(stats(5));
+    assert_eq!(
+        r,
+        Stats {
+            total: Sum(15),
+            peak: Max(7),
+            count: Count(3),
+        }
+    );
+
+    let r = Pair(String::from("a"), vec![1]).op(Pair(String::from("b"), vec![2]));
+    assert_eq!(r, Pair(String::from("ab"), vec![1, 2]));
+
+    assert_eq!(Marker.op(Marker), Marker);
+}
@@ -0,0 +1,360 @@
+//! Conflict-free replicated data types: states that replicas update independently and merge with
+//! `op`. Merging is commutative and idempotent on top of being associative, so replicas converge
+//! to the same state whatever the order and number of times they exchange their states in.
+
+use crate::traits::*;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+/// Identifies a replica, which should be unique among the replicas sharing a state.
+pub type ReplicaId = u64;
+
+/// A counter which can only grow, each replica incrementing its own count. Merging keeps the
+/// highest count of every replica, and the value is the sum of the counts.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GCounter {
+    counts: HashMap<ReplicaId, u64>,
+}
+
+#[cfg(feature = "std")]
+impl GCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `n` to the count of `replica`.
+    pub fn increment(&mut self, replica: ReplicaId, n: u64) {
+        *self.counts.entry(replica).or_insert(0) += n;
+    }
+
+    /// Returns the count of `replica` alone.
+    pub fn count(&self, replica: ReplicaId) -> u64 {
+        self.counts.get(&replica).copied().unwrap_or(0)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.counts.values().sum()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Semigroup for GCounter {
+    fn op(self, other: Self) -> Self {
+        let (mut into, from) = if self.counts.len() >= other.counts.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        for (replica, n) in from.counts {
+            let count = into.counts.entry(replica).or_insert(0);
+            *count = n.max(*count);
+        }
+        into
+    }
+}
+
+#[cfg(feature = "std")]
+impl DefaultMonoid for GCounter {}
+#[cfg(feature = "std")]
+impl CommutativeMonoid for GCounter {}
+#[cfg(feature = "std")]
+impl Band for GCounter {}
+#[cfg(feature = "std")]
+impl Semilattice for GCounter {}
+
+/// A counter which can grow and shrink, as a pair of grow-only counters of the increments and the
+/// decrements, merged separately.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PNCounter {
+    p: GCounter,
+    n: GCounter,
+}
+
+#[cfg(feature = "std")]
+impl PNCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `n` to the counter on behalf of `replica`.
+    pub fn increment(&mut self, replica: ReplicaId, n: u64) {
+        self.p.increment(replica, n);
+    }
+
+    /// Subtracts `n` from the counter on behalf of `replica`.
+    pub fn decrement(&mut self, replica: ReplicaId, n: u64) {
+        self.n.increment(replica, n);
+    }
+
+    /// Returns the increments minus the decrements. The totals of each only need to fit in a
+    /// `u64`, as long as their difference fits in an `i64`.
+    pub fn value(&self) -> i64 {
+        self.p.value().wrapping_sub(self.n.value()) as i64
+    }
+}
+
+#[cfg(feature = "std")]
+impl Semigroup for PNCounter {
+    fn op(self, other: Self) -> Self {
+        Self {
+            p: self.p.op(other.p),
+            n: self.n.op(other.n),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl DefaultMonoid for PNCounter {}
+#[cfg(feature = "std")]
+impl CommutativeMonoid for PNCounter {}
+#[cfg(feature = "std")]
+impl Band for PNCounter {}
+#[cfg(feature = "std")]
+impl Semilattice for PNCounter {}
+
+/// A register holding the value written last, according to the timestamps attached to the
+/// writes. Unlike `Last`, the result doesn't depend on the order the states are merged in.
+///
+/// When two writes have the same timestamp, the larger value wins, so that every replica settles
+/// on the same one. There is no identity, as an empty register would have no value to hold; use
+/// `Option<LwwRegister<T>>` for registers that may not have been written to yet.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LwwRegister<T> {
+    value: T,
+    timestamp: u64,
+}
+
+impl<T> LwwRegister<T> {
+    pub fn new(value: T, timestamp: u64) -> Self {
+        Self { value, timestamp }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Ord> LwwRegister<T> {
+    /// Writes `value` at `timestamp`, unless the register already holds a later write.
+    pub fn set(&mut self, value: T, timestamp: u64) {
+        if (timestamp, &value) > (self.timestamp, &self.value) {
+            *self = Self::new(value, timestamp);
+        }
+    }
+}
+
+impl<T: Ord> Semigroup for LwwRegister<T> {
+    fn op(self, other: Self) -> Self {
+        if (other.timestamp, &other.value) > (self.timestamp, &self.value) {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<T: Ord> Band for LwwRegister<T> {}
+
+/// Identifies an addition to an `OrSet`, by the replica making it and the number of additions
+/// that replica made up to it.
+#[cfg(feature = "std")]
+type Tag = (ReplicaId, u64);
+
+/// A set supporting both additions and removals, where removing a value only cancels the
+/// additions of it the replica has observed. When a value is added and removed concurrently, the
+/// addition wins, while merging with a state from before a removal doesn't bring the value back.
+///
+/// Removed additions are kept as tombstones, so the state grows with every addition.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrSet<T: Eq + Hash> {
+    adds: HashMap<T, HashSet<Tag>>,
+    removes: HashSet<Tag>,
+    clock: GCounter,
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> Default for OrSet<T> {
+    fn default() -> Self {
+        Self {
+            adds: HashMap::new(),
+            removes: HashSet::new(),
+            clock: GCounter::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> OrSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `value` on behalf of `replica`.
+    pub fn add(&mut self, replica: ReplicaId, value: T) {
+        self.clock.increment(replica, 1);
+        let tag = (replica, self.clock.count(replica));
+        self.adds.entry(value).or_default().insert(tag);
+    }
+
+    /// Removes `value`, cancelling the additions of it observed so far.
+    pub fn remove(&mut self, value: &T) {
+        if let Some(tags) = self.adds.get(value) {
+            self.removes.extend(tags);
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.adds
+            .get(value)
+            .is_some_and(|tags| !tags.is_subset(&self.removes))
+    }
+
+    /// Returns the values in the set.
+    pub fn value(&self) -> HashSet<T>
+    where
+        T: Clone,
+    {
+        self.adds
+            .iter()
+            .filter(|(_, tags)| !tags.is_subset(&self.removes))
+            .map(|(value, _)| value.clone())
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> Semigroup for OrSet<T> {
+    fn op(self, other: Self) -> Self {
+        Self {
+            adds: self.adds.op(other.adds),
+            removes: self.removes.op(other.removes),
+            clock: self.clock.op(other.clock),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> DefaultMonoid for OrSet<T> {}
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> CommutativeMonoid for OrSet<T> {}
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> Band for OrSet<T> {}
+#[cfg(feature = "std")]
+impl<T: Eq + Hash> Semilattice for OrSet<T> {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::crdt::{GCounter, LwwRegister, OrSet, PNCounter};
+    use crate::traits::*;
+
+    #[test]
+    fn gcounter_merges_replicas() {
+        let mut a = GCounter::new();
+        let mut b = GCounter::new();
+        a.increment(1, 3);
+        b.increment(2, 2);
+        b.increment(2, 1);
+        let merged = a.clone().op(b.clone());
+        assert_eq!(merged.value(), 6);
+        assert_eq!(merged, b.clone().op(a.clone()));
+        assert_eq!(merged.count(2), 3);
+
+        // A stale state of `a` doesn't undo its later increments
+        let stale = a.clone();
+        a.increment(1, 4);
+        assert_eq!(merged.op(a).op(stale).value(), 10);
+    }
+
+    #[test]
+    fn gcounter_merge_idempotent() {
+        let mut a = GCounter::new();
+        a.increment(1, 5);
+        a.increment(7, 2);
+        assert_eq!(a.clone().op(a.clone()), a);
+        assert_eq!(a.clone().op(GCounter::empty()), a);
+    }
+
+    #[test]
+    fn pncounter_increments_and_decrements() {
+        let mut a = PNCounter::new();
+        let mut b = PNCounter::new();
+        a.increment(1, 10);
+        a.decrement(1, 3);
+        b.decrement(2, 9);
+        let merged = a.clone().op(b.clone());
+        assert_eq!(merged.value(), -2);
+        assert_eq!(merged, b.clone().op(a.clone()));
+        assert_eq!(merged.clone().op(merged.clone()), merged);
+
+        b.increment(2, 5);
+        let merged = merged.op(b).op(a);
+        assert_eq!(merged.value(), 3);
+        assert_eq!(PNCounter::empty().value(), 0);
+    }
+
+    #[test]
+    fn lww_register_keeps_latest_write() {
+        let writes = vec![
+            LwwRegister::new("b", 2),
+            LwwRegister::new("c", 5),
+            LwwRegister::new("a", 1),
+        ];
+        let forward = Semigroup::concat(writes.clone()).unwrap();
+        let backward = Semigroup::concat(writes.into_iter().rev()).unwrap();
+        assert_eq!(forward, backward);
+        assert_eq!(*forward.get(), "c");
+
+        let tie = LwwRegister::new("x", 5);
+        assert_eq!(forward.op(tie).into_inner(), "x");
+        assert_eq!(tie.op(forward).into_inner(), "x");
+
+        let mut reg = LwwRegister::new(1, 10);
+        reg.set(2, 3);
+        assert_eq!(reg, LwwRegister::new(1, 10));
+        reg.set(2, 11);
+        assert_eq!((*reg.get(), reg.timestamp()), (2, 11));
+    }
+
+    #[test]
+    fn or_set_concurrent_add_wins() {
+        let mut a = OrSet::new();
+        a.add(1, "x");
+        let mut b = a.clone();
+        b.remove(&"x");
+        a.add(1, "x");
+        assert!(!b.contains(&"x"));
+
+        let merged = a.clone().op(b.clone());
+        assert_eq!(merged, b.op(a));
+        assert!(merged.contains(&"x"));
+        assert_eq!(merged.clone().op(merged.clone()), merged);
+    }
+
+    #[test]
+    fn or_set_remove_does_not_resurrect() {
+        let mut a = OrSet::new();
+        a.add(1, 'x');
+        a.add(1, 'y');
+        let stale = a.clone();
+        let mut b = a.clone();
+        b.remove(&'x');
+        b.add(2, 'z');
+
+        let merged = stale.op(b).op(a);
+        assert_eq!(merged.value(), ['y', 'z'].iter().copied().collect());
+        assert!(OrSet::<char>::empty().value().is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::functor::Functor;
+use crate::traits::*;
+
+/// A value of one of two types.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// Keeps the first `Right` value; when both sides are `Left`, the last one is kept.
+impl<L, R> Semigroup for Either<L, R> {
+    fn op(self, other: Self) -> Self {
+        match self {
+            Either::Left(_) => other,
+            right => right,
+        }
+    }
+}
+
+impl<L, R> Either<L, R> {
+    /// Maps each side with its own function.
+    pub fn bimap<M, S, F: FnOnce(L) -> M, G: FnOnce(R) -> S>(self, f: F, g: G) -> Either<M, S> {
+        match self {
+            Either::Left(l) => Either::Left(f(l)),
+            Either::Right(r) => Either::Right(g(r)),
+        }
+    }
+}
+
+/// Maps the `Right` value, leaving `Left` values untouched.
+impl<L, R, S> Functor<S> for Either<L, R> {
+    type Item = R;
+    type Wrapped = Either<L, S>;
+
+    fn fmap<F: FnMut(R) -> S>(self, f: F) -> Either<L, S> {
+        self.bimap(|l| l, f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::either::Either::{self, Left, Right};
+    use crate::functor::Functor;
+    use crate::traits::*;
+
+    #[test]
+    fn either_right_bias() {
+        assert_eq!(Left::<i32, i32>(1).op(Right(2)), Right(2));
+        assert_eq!(Right::<i32, i32>(1).op(Left(2)), Right(1));
+        assert_eq!(Right::<i32, i32>(1).op(Right(2)), Right(1));
+        assert_eq!(Left::<i32, i32>(1).op(Left(2)), Left(2));
+        let v = vec![Left("a"), Left("b"), Right(3), Right(4)];
+        assert_eq!(Semigroup::concat(v), Some(Right(3)));
+    }
+
+    #[test]
+    fn either_bimap() {
+        let f = |l: &str| l.len();
+        let g = |r: i32| r * 2;
+        assert_eq!(Left::<&str, i32>("abc").bimap(f, g), Left(3));
+        assert_eq!(Right::<&str, i32>(4).bimap(f, g), Right(8));
+    }
+
+    #[test]
+    fn either_functor() {
+        let e: Either<&str, i32> = Right(1);
+        assert_eq!(e.fmap(|x| x + 1), Right(2));
+        let e: Either<&str, i32> = Left("error");
+        assert_eq!(e.fmap(|x| x + 1), Left("error"));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::traits::*;
+use crate::Sum;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Structures whose elements can be mapped into a monoid and combined in order, generalizing
+/// `Monoid::concat` over an iterator.
+pub trait Foldable: Sized {
+    type Item;
+
+    fn fold_map<M: Monoid, F: Fn(Self::Item) -> M>(self, f: F) -> M;
+
+    /// Combines the elements, which themselves form a monoid.
+    fn fold_monoid(self) -> Self::Item
+    where
+        Self::Item: Monoid,
+    {
+        self.fold_map(|x| x)
+    }
+
+    fn length(self) -> usize {
+        self.fold_map(|_| Sum(1)).0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Foldable for Vec<T> {
+    type Item = T;
+
+    fn fold_map<M: Monoid, F: Fn(T) -> M>(self, f: F) -> M {
+        Monoid::concat(self.into_iter().map(f))
+    }
+
+    fn length(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Foldable for Option<T> {
+    type Item = T;
+
+    fn fold_map<M: Monoid, F: Fn(T) -> M>(self, f: F) -> M {
+        self.map_or_else(M::empty, f)
+    }
+}
+
+impl<T, const N: usize> Foldable for [T; N] {
+    type Item = T;
+
+    fn fold_map<M: Monoid, F: Fn(T) -> M>(self, f: F) -> M {
+        Monoid::concat(IntoIterator::into_iter(self).map(f))
+    }
+
+    fn length(self) -> usize {
+        N
+    }
+}
+
+/// Maps are folded over their values, in key order.
+#[cfg(feature = "alloc")]
+impl<K, V> Foldable for BTreeMap<K, V> {
+    type Item = V;
+
+    fn fold_map<M: Monoid, F: Fn(V) -> M>(self, f: F) -> M {
+        Monoid::concat(self.into_values().map(f))
+    }
+
+    fn length(self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::foldable::Foldable;
+    use crate::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn vec_fold_map() {
+        let v = vec![3, 1, 4, 1, 5];
+        assert_eq!(v.clone().fold_map(Sum), Sum(14));
+        assert_eq!(v.clone().fold_map(Max), Max(5));
+        assert_eq!(v.fold_map(Product), Product(60));
+    }
+
+    #[test]
+    fn option_fold_map() {
+        assert_eq!(Some(3).fold_map(Sum), Sum(3));
+        assert_eq!(None.fold_map(Sum), Sum(0));
+        assert_eq!(Some(2).length(), 1);
+    }
+
+    #[test]
+    fn array_fold_monoid() {
+        let a = [String::from("a"), String::from("b"), String::from("c")];
+        assert_eq!(a.fold_monoid(), "abc");
+        assert_eq!([0u8; 4].length(), 4);
+    }
+
+    #[test]
+    fn btreemap_fold_map() {
+        let m: BTreeMap<_, _> = vec![(2, "b"), (1, "a"), (3, "c")].into_iter().collect();
+        assert_eq!(m.clone().fold_map(String::from), "abc");
+        assert_eq!(m.length(), 3);
+    }
+}
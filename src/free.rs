@@ -0,0 +1,82 @@
+use crate::traits::*;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+/// The free monoid over `T`: sequences of values, combining by concatenation, without
+/// interpreting them yet. Any function from `T` into a monoid extends to the whole sequence
+/// with [`FreeMonoid::fold_map`], so that a sequence built once can be collapsed into `Sum`,
+/// `String` or any other monoid afterwards.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FreeMonoid<T>(pub Vec<T>);
+
+impl<T> Default for FreeMonoid<T> {
+    fn default() -> Self {
+        FreeMonoid(Vec::new())
+    }
+}
+
+impl<T> FreeMonoid<T> {
+    pub fn singleton(x: T) -> Self {
+        FreeMonoid(alloc::vec![x])
+    }
+
+    pub fn push(&mut self, x: T) {
+        self.0.push(x);
+    }
+
+    /// Maps every value into the monoid `M` and combines the results in order.
+    pub fn fold_map<M: Monoid, F: FnMut(T) -> M>(self, f: F) -> M {
+        Monoid::concat(self.0.into_iter().map(f))
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> From<Vec<T>> for FreeMonoid<T> {
+    fn from(v: Vec<T>) -> Self {
+        FreeMonoid(v)
+    }
+}
+
+impl<T> FromIterator<T> for FreeMonoid<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        FreeMonoid(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for FreeMonoid<T> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T> Semigroup for FreeMonoid<T> {
+    fn op(self, other: Self) -> Self {
+        FreeMonoid(self.0.op(other.0))
+    }
+}
+
+impl<T> DefaultMonoid for FreeMonoid<T> {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::free::FreeMonoid;
+    use crate::traits::*;
+    use crate::{Product, Sum};
+
+    #[test]
+    fn free_monoid_interprets() {
+        let xs = FreeMonoid::from(vec![1, 2]).op(FreeMonoid::singleton(3));
+        let xs = xs.op(FreeMonoid::empty()).op((4..=5).collect());
+        assert_eq!(xs.clone().into_vec(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(xs.clone().fold_map(Sum), Sum(15));
+        assert_eq!(xs.clone().fold_map(Product), Product(120));
+        assert_eq!(xs.fold_map(|x| x.to_string()), "12345");
+        assert_eq!(FreeMonoid::<i32>::empty().fold_map(Product), Product(1));
+    }
+}
@@ -0,0 +1,795 @@
+#[cfg(feature = "alloc")]
+use crate::either::Either;
+use crate::traits::*;
+#[cfg(feature = "alloc")]
+use crate::Predicate;
+use crate::{Max, Min, Product, Sum};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Types wrapping values of type `Item`, which can be transformed into values of type `B`,
+/// producing `Wrapped`.
+///
+/// Implementations must preserve the structure around the values, which is expressed by the
+/// functor laws:
+///
+/// - identity: `x.fmap(|a| a) == x`
+/// - composition: `x.fmap(f).fmap(g) == x.fmap(|a| g(f(a)))`
+pub trait Functor<B> {
+    type Item;
+    type Wrapped;
+
+    fn fmap<F: FnMut(Self::Item) -> B>(self, f: F) -> Self::Wrapped;
+}
+
+impl<A, B> Functor<B> for Option<A> {
+    type Item = A;
+    type Wrapped = Option<B>;
+
+    fn fmap<F: FnMut(A) -> B>(self, f: F) -> Option<B> {
+        self.map(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A, B> Functor<B> for Vec<A> {
+    type Item = A;
+    type Wrapped = Vec<B>;
+
+    fn fmap<F: FnMut(A) -> B>(self, f: F) -> Vec<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+macro_rules! impl_functor_newtype {
+    ($($name:ident)*) => {$(
+        impl<A, B> Functor<B> for $name<A> {
+            type Item = A;
+            type Wrapped = $name<B>;
+
+            fn fmap<F: FnMut(A) -> B>(self, mut f: F) -> $name<B> {
+                $name(f(self.0))
+            }
+        }
+    )*};
+}
+
+impl_functor_newtype!(Min Max Sum Product);
+
+/// Functors which can lift a value with `pure`, and apply functions held in the same kind of
+/// structure with `apply`.
+///
+/// `WrappedFn<F>` is the structure holding functions `F`, e.g. `Option<F>` for `Option`.
+pub trait Applicative<B>: Functor<B> + Sized {
+    type WrappedFn<F>;
+
+    fn pure(x: Self::Item) -> Self;
+    fn apply<F: FnMut(Self::Item) -> B>(self, f: Self::WrappedFn<F>) -> Self::Wrapped;
+
+    /// Combines `self` and `other` with a binary function, by partially applying `f` to the
+    /// elements of `self` and applying the resulting functions to `other`.
+    #[cfg(feature = "alloc")]
+    fn zip_with<'a, A, O, F>(self, other: O, f: F) -> O::Wrapped
+    where
+        Self: Functor<
+            Box<dyn FnMut(O::Item) -> B + 'a>,
+            Item = A,
+            Wrapped = O::WrappedFn<Box<dyn FnMut(O::Item) -> B + 'a>>,
+        >,
+        O: Applicative<B>,
+        A: Clone + 'a,
+        F: FnMut(A, O::Item) -> B + Clone + 'a,
+    {
+        let fs = Functor::fmap(self, |a: A| {
+            let mut f = f.clone();
+            Box::new(move |c| f(a.clone(), c)) as Box<dyn FnMut(O::Item) -> B + 'a>
+        });
+        other.apply(fs)
+    }
+}
+
+impl<A, B> Applicative<B> for Option<A> {
+    type WrappedFn<F> = Option<F>;
+
+    fn pure(x: A) -> Self {
+        Some(x)
+    }
+
+    fn apply<F: FnMut(A) -> B>(self, f: Option<F>) -> Option<B> {
+        match (self, f) {
+            (Some(x), Some(mut f)) => Some(f(x)),
+            _ => None,
+        }
+    }
+}
+
+/// Applying a vector of functions to a vector of values gives every combination, in order of
+/// the functions then the values.
+#[cfg(feature = "alloc")]
+impl<A: Clone, B> Applicative<B> for Vec<A> {
+    type WrappedFn<F> = Vec<F>;
+
+    fn pure(x: A) -> Self {
+        alloc::vec![x]
+    }
+
+    fn apply<F: FnMut(A) -> B>(self, fs: Vec<F>) -> Vec<B> {
+        let mut res = Vec::with_capacity(fs.len() * self.len());
+        for mut f in fs {
+            res.extend(self.iter().cloned().map(&mut f));
+        }
+        res
+    }
+}
+
+/// Applicatives which can chain computations producing new structures, with `bind`.
+///
+/// Implementations must obey the monad laws, where `pure` is `Applicative::pure`:
+///
+/// - left identity: `pure(a).bind(f) == f(a)`
+/// - right identity: `m.bind(pure) == m`
+/// - associativity: `m.bind(f).bind(g) == m.bind(|x| f(x).bind(g))`
+pub trait Monad<B>: Applicative<B> {
+    fn bind<F: FnMut(Self::Item) -> Self::Wrapped>(self, f: F) -> Self::Wrapped;
+
+    /// Removes one level of nesting, e.g. turning `Some(Some(x))` into `Some(x)`.
+    fn flatten(self) -> Self::Wrapped
+    where
+        Self: Functor<B, Item = <Self as Functor<B>>::Wrapped>,
+    {
+        self.bind(|x| x)
+    }
+}
+
+impl<A, B> Monad<B> for Option<A> {
+    fn bind<F: FnMut(A) -> Option<B>>(self, f: F) -> Option<B> {
+        self.and_then(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Clone, B> Monad<B> for Vec<A> {
+    fn bind<F: FnMut(A) -> Vec<B>>(self, f: F) -> Vec<B> {
+        self.into_iter().flat_map(f).collect()
+    }
+}
+
+/// Effects which can be combined into a single effect holding a pair of their values, the way
+/// `Applicative` combines a function with its argument.
+///
+/// `product` must be associative, up to the nesting of the pairs:
+/// `a.product(b).product(c)` and `a.product(b.product(c))` hold the same values.
+pub trait Semigroupal<B>: Sized {
+    type Item;
+    /// The same effect, holding values of type `B`.
+    type Other;
+    /// The same effect, holding pairs `(Self::Item, B)`.
+    type Product;
+
+    fn product(self, other: Self::Other) -> Self::Product;
+}
+
+/// Pairs the values when both are present.
+impl<A, B> Semigroupal<B> for Option<A> {
+    type Item = A;
+    type Other = Option<B>;
+    type Product = Option<(A, B)>;
+
+    fn product(self, other: Option<B>) -> Option<(A, B)> {
+        self.zip(other)
+    }
+}
+
+/// Pairs the values of two `Ok`s, or returns the first `Err`. See `Validation` for accumulating
+/// every error instead.
+impl<A, B, E> Semigroupal<B> for Result<A, E> {
+    type Item = A;
+    type Other = Result<B, E>;
+    type Product = Result<(A, B), E>;
+
+    fn product(self, other: Result<B, E>) -> Result<(A, B), E> {
+        Ok((self?, other?))
+    }
+}
+
+/// Pairs every value of `self` with every value of `other`, in the order of `Applicative`.
+#[cfg(feature = "alloc")]
+impl<A: Clone, B: Clone> Semigroupal<B> for Vec<A> {
+    type Item = A;
+    type Other = Vec<B>;
+    type Product = Vec<(A, B)>;
+
+    fn product(self, other: Vec<B>) -> Vec<(A, B)> {
+        let mut res = Vec::with_capacity(self.len() * other.len());
+        for a in self {
+            res.extend(other.iter().map(|b| (a.clone(), b.clone())));
+        }
+        res
+    }
+}
+
+/// Structures with a choice between alternatives, as a monoid on the structure itself rather than
+/// on the values it holds: `alt` works without any bound on them. `Option` keeps the first
+/// `Some`, where its `Semigroup` instance would combine the values of two `Some`s instead.
+///
+/// `alt` must be associative, with `empty_alt` as its identity. It is typically used along
+/// with `Applicative`, trying parsers or fallbacks in turn.
+pub trait Alternative: Sized {
+    /// The alternative which always fails.
+    fn empty_alt() -> Self;
+    /// Chooses between `self` and `other`.
+    fn alt(self, other: Self) -> Self;
+}
+
+/// Keeps the first `Some`, like `Option::or`.
+impl<A> Alternative for Option<A> {
+    fn empty_alt() -> Self {
+        None
+    }
+
+    fn alt(self, other: Self) -> Self {
+        self.or(other)
+    }
+}
+
+/// Keeps every alternative, in order.
+#[cfg(feature = "alloc")]
+impl<A> Alternative for Vec<A> {
+    fn empty_alt() -> Self {
+        Vec::new()
+    }
+
+    fn alt(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+}
+
+/// A plain value, as a monad without any effect, for running code written against an arbitrary
+/// monad directly. Its algebraic instances are those of `T`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Identity<T>(pub T);
+
+impl<T: Semigroup> Semigroup for Identity<T> {
+    fn op(self, other: Self) -> Self {
+        Identity(self.0.op(other.0))
+    }
+}
+
+impl<T: Monoid> Monoid for Identity<T> {
+    fn empty() -> Self {
+        Identity(T::empty())
+    }
+}
+
+impl_functor_newtype!(Identity);
+
+impl<A, B> Applicative<B> for Identity<A> {
+    type WrappedFn<F> = Identity<F>;
+
+    fn pure(x: A) -> Self {
+        Identity(x)
+    }
+
+    fn apply<F: FnMut(A) -> B>(self, mut f: Identity<F>) -> Identity<B> {
+        Identity((f.0)(self.0))
+    }
+}
+
+impl<A, B> Monad<B> for Identity<A> {
+    fn bind<F: FnMut(A) -> Identity<B>>(self, mut f: F) -> Identity<B> {
+        f(self.0)
+    }
+}
+
+/// A value of type `A` standing in for a structure of `B`s which holds none of them: mapping
+/// over the `B`s leaves the `A` as it is, and applying combines the `A`s as a monoid.
+///
+/// Traversing with `Const` therefore folds the values of a structure into a monoid.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Const<A, B>(pub A, PhantomData<B>);
+
+impl<A, B> Const<A, B> {
+    pub fn new(a: A) -> Self {
+        Const(a, PhantomData)
+    }
+
+    pub fn into_inner(self) -> A {
+        self.0
+    }
+}
+
+impl<A: Semigroup, B> Semigroup for Const<A, B> {
+    fn op(self, other: Self) -> Self {
+        Const::new(self.0.op(other.0))
+    }
+}
+
+impl<A: Monoid, B> Monoid for Const<A, B> {
+    fn empty() -> Self {
+        Const::new(A::empty())
+    }
+}
+
+impl<A, B, C> Functor<C> for Const<A, B> {
+    type Item = B;
+    type Wrapped = Const<A, C>;
+
+    fn fmap<F: FnMut(B) -> C>(self, _: F) -> Const<A, C> {
+        Const::new(self.0)
+    }
+}
+
+impl<A: Monoid, B, C> Applicative<C> for Const<A, B> {
+    type WrappedFn<F> = Const<A, F>;
+
+    fn pure(_: B) -> Self {
+        Const::new(A::empty())
+    }
+
+    fn apply<F: FnMut(B) -> C>(self, f: Const<A, F>) -> Const<A, C> {
+        Const::new(f.0.op(self.0))
+    }
+}
+
+/// A natural transformation, turning an `F<A>` into a `G<A>` for every `A` by changing the
+/// structure only, without inspecting or creating values of type `A`.
+///
+/// Implementations must commute with mapping, which is the naturality law:
+/// `Self::transform(x.fmap(f)) == Self::transform(x).fmap(f)`.
+pub trait Transform {
+    type From<A>;
+    type To<A>;
+
+    fn transform<A>(fa: Self::From<A>) -> Self::To<A>;
+}
+
+/// Turns `None` into an empty vector and `Some(x)` into `[x]`.
+#[cfg(feature = "alloc")]
+pub struct OptionToVec;
+
+#[cfg(feature = "alloc")]
+impl Transform for OptionToVec {
+    type From<A> = Option<A>;
+    type To<A> = Vec<A>;
+
+    fn transform<A>(fa: Option<A>) -> Vec<A> {
+        fa.into_iter().collect()
+    }
+}
+
+/// Turns `Ok(x)` into `Some(x)`, discarding the error of an `Err`.
+pub struct ResultToOption<E>(PhantomData<E>);
+
+impl<E> Transform for ResultToOption<E> {
+    type From<A> = Result<A, E>;
+    type To<A> = Option<A>;
+
+    fn transform<A>(fa: Result<A, E>) -> Option<A> {
+        fa.ok()
+    }
+}
+
+/// Transformations from `A` to `B` which can be adapted on both ends: contravariantly on their
+/// input, like `Contravariant` adapts a `Predicate`, and covariantly on their output, like
+/// `Functor`.
+///
+/// Implemented for every function, including boxed ones.
+pub trait Profunctor<A, B>: Sized {
+    /// Runs `pre` on the input and `post` on the output.
+    fn dimap<C, D>(self, pre: impl Fn(C) -> A, post: impl Fn(B) -> D) -> impl Fn(C) -> D;
+
+    /// Adapts the input only.
+    fn lmap<C>(self, pre: impl Fn(C) -> A) -> impl Fn(C) -> B {
+        self.dimap(pre, |b| b)
+    }
+
+    /// Adapts the output only.
+    fn rmap<D>(self, post: impl Fn(B) -> D) -> impl Fn(A) -> D {
+        self.dimap(|a| a, post)
+    }
+}
+
+impl<A, B, F: Fn(A) -> B> Profunctor<A, B> for F {
+    fn dimap<C, D>(self, pre: impl Fn(C) -> A, post: impl Fn(B) -> D) -> impl Fn(C) -> D {
+        move |c| post(self(pre(c)))
+    }
+}
+
+/// Consumers of values of type `B`, which can be adapted to consume values of type `A` by
+/// projecting them to `B` first.
+///
+/// The projection borrows its input, so that the adapted predicate doesn't need to own it.
+#[cfg(feature = "alloc")]
+pub trait Contravariant<A: ?Sized, B> {
+    fn contramap<F: Fn(&A) -> B + 'static>(self, f: F) -> Predicate<A>;
+}
+
+#[cfg(feature = "alloc")]
+impl<A: ?Sized, B: 'static> Contravariant<A, B> for Predicate<B> {
+    fn contramap<F: Fn(&A) -> B + 'static>(self, f: F) -> Predicate<A> {
+        Predicate::new(move |a| self.call(&f(a)))
+    }
+}
+
+/// Contravariant consumers which can be combined structurally, by splitting an input into parts
+/// and consuming each part with its own consumer.
+#[cfg(feature = "alloc")]
+pub trait Divisible<B: ?Sized>: Sized {
+    /// Splits inputs of type `A` into a `B` checked by `self` and a `C` checked by `other`, and
+    /// accepts them when both parts are accepted.
+    fn divide<A: ?Sized, C: ?Sized + 'static, F>(self, f: F, other: Predicate<C>) -> Predicate<A>
+    where
+        F: for<'a> Fn(&'a A) -> (&'a B, &'a C) + 'static;
+
+    /// The consumer accepting everything, neutral for `divide`.
+    fn conquer() -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<B: ?Sized + 'static> Divisible<B> for Predicate<B> {
+    fn divide<A: ?Sized, C: ?Sized + 'static, F>(self, f: F, other: Predicate<C>) -> Predicate<A>
+    where
+        F: for<'a> Fn(&'a A) -> (&'a B, &'a C) + 'static,
+    {
+        Predicate::new(move |a| {
+            let (b, c) = f(a);
+            self.call(b) && other.call(c)
+        })
+    }
+
+    fn conquer() -> Self {
+        Predicate::new(|_| true)
+    }
+}
+
+/// Divisible consumers which can also route each input to one of two consumers.
+#[cfg(feature = "alloc")]
+pub trait Decidable<B: ?Sized>: Divisible<B> {
+    /// Routes inputs of type `A` either to `self` as a `B`, or to `other` as a `C`.
+    fn choose<A: ?Sized, C: ?Sized + 'static, F>(self, f: F, other: Predicate<C>) -> Predicate<A>
+    where
+        F: for<'a> Fn(&'a A) -> Either<&'a B, &'a C> + 'static;
+}
+
+#[cfg(feature = "alloc")]
+impl<B: ?Sized + 'static> Decidable<B> for Predicate<B> {
+    fn choose<A: ?Sized, C: ?Sized + 'static, F>(self, f: F, other: Predicate<C>) -> Predicate<A>
+    where
+        F: for<'a> Fn(&'a A) -> Either<&'a B, &'a C> + 'static,
+    {
+        Predicate::new(move |a| match f(a) {
+            Either::Left(b) => self.call(b),
+            Either::Right(c) => other.call(c),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::either::Either;
+    use crate::functor::{
+        Alternative, Applicative, Const, Contravariant, Decidable, Divisible, Functor, Identity,
+        Monad, OptionToVec, Profunctor, ResultToOption, Semigroupal, Transform,
+    };
+    use crate::*;
+
+    fn f(x: i32) -> i32 {
+        x + 1
+    }
+
+    fn g(x: i32) -> i32 {
+        x * 2
+    }
+
+    #[test]
+    fn sum_functor() {
+        assert_eq!(Functor::fmap(Sum(3), |x| x + 1), Sum(4));
+        assert_eq!(Functor::fmap(Sum(3), |x| x), Sum(3));
+        assert_eq!(
+            Functor::fmap(Functor::fmap(Sum(3), f), g),
+            Functor::fmap(Sum(3), |x| g(f(x)))
+        );
+    }
+
+    #[test]
+    fn wrappers_functor() {
+        assert_eq!(Functor::fmap(Min(3), |x| x), Min(3));
+        assert_eq!(
+            Functor::fmap(Functor::fmap(Min(3), f), g),
+            Functor::fmap(Min(3), |x| g(f(x)))
+        );
+        assert_eq!(Functor::fmap(Max(3), |x| x), Max(3));
+        assert_eq!(
+            Functor::fmap(Functor::fmap(Max(3), f), g),
+            Functor::fmap(Max(3), |x| g(f(x)))
+        );
+        assert_eq!(Functor::fmap(Product(3), |x| x), Product(3));
+        assert_eq!(
+            Functor::fmap(Functor::fmap(Product(3), f), g),
+            Functor::fmap(Product(3), |x| g(f(x)))
+        );
+    }
+
+    #[test]
+    fn option_functor() {
+        assert_eq!(Some(3).fmap(|x| x), Some(3));
+        assert_eq!(Some(3).fmap(f).fmap(g), Some(3).fmap(|x| g(f(x))));
+        assert_eq!(None.fmap(f).fmap(g), None);
+    }
+
+    #[test]
+    fn vec_functor() {
+        let v = vec![1, 2, 3];
+        assert_eq!(v.clone().fmap(|x| x), v);
+        assert_eq!(v.clone().fmap(f).fmap(g), v.fmap(|x| g(f(x))));
+        assert_eq!(Vec::new().fmap(f), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn option_applicative() {
+        assert_eq!(Some(3).apply(Some(f)), Some(4));
+        assert_eq!(None.apply(Some(f)), None);
+        assert_eq!(Some(3).apply(None::<fn(i32) -> i32>), None);
+        assert_eq!(
+            <Option<_> as Applicative<i32>>::pure(3).apply(Some(g)),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn option_zip_with() {
+        // `Option::zip_with` is an unstable inherent method, which would shadow the trait method
+        let r = Applicative::zip_with(Some(3), Some(4), |a, b| a * b);
+        assert_eq!(r, Some(12));
+        let r = Applicative::zip_with(None, Some(4), |a: i32, b| a * b);
+        assert_eq!(r, None);
+        let r = Applicative::zip_with(Some(3), None, |a, b: i32| a * b);
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn vec_applicative() {
+        let fs: Vec<fn(i32) -> i32> = vec![f, g];
+        assert_eq!(vec![1, 2, 3].apply(fs), vec![2, 3, 4, 2, 4, 6]);
+        assert_eq!(
+            <Vec<_> as Applicative<i32>>::pure(5).apply(vec![f]),
+            vec![6]
+        );
+    }
+
+    #[test]
+    fn vec_zip_with() {
+        let r = vec![1, 2].zip_with(vec![10, 20, 30], |a, b| a + b);
+        assert_eq!(r, vec![11, 21, 31, 12, 22, 32]);
+        let r = Vec::<i32>::new().zip_with(vec![1], |a, b| a + b);
+        assert!(r.is_empty());
+    }
+
+    fn parse(s: &str) -> Option<i32> {
+        s.parse().ok()
+    }
+
+    fn half(x: i32) -> Option<i32> {
+        if x % 2 == 0 {
+            Some(x / 2)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn option_monad() {
+        assert_eq!(Some("42").bind(parse).bind(half), Some(21));
+        assert_eq!(Some("21").bind(parse).bind(half), None);
+        assert_eq!(Some("nope").bind(parse).bind(half), None);
+        assert_eq!(Monad::flatten(Some(Some(3))), Some(3));
+        assert_eq!(Monad::flatten(Some(None::<i32>)), None);
+    }
+
+    #[test]
+    fn option_monad_laws() {
+        let pure = <Option<i32> as Applicative<i32>>::pure;
+        for m in [Some(4), Some(3), None] {
+            assert_eq!(m.bind(pure), m);
+            assert_eq!(m.bind(half).bind(half), m.bind(|x| half(x).bind(half)));
+        }
+        assert_eq!(pure(4).bind(half), half(4));
+    }
+
+    #[test]
+    fn vec_monad() {
+        let v = vec![1, 2, 3].bind(|x| vec![x; x as usize]);
+        assert_eq!(v, vec![1, 2, 2, 3, 3, 3]);
+        assert_eq!(
+            Monad::flatten(vec![vec![1], vec![], vec![2, 3]]),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn vec_monad_laws() {
+        let pure = <Vec<i32> as Applicative<i32>>::pure;
+        let f = |x: i32| vec![x, x + 1];
+        let g = |x: i32| vec![x * 10];
+        let m = vec![1, 5];
+        assert_eq!(pure(3).bind(f), f(3));
+        assert_eq!(m.clone().bind(pure), m);
+        assert_eq!(m.clone().bind(f).bind(g), m.bind(|x| f(x).bind(g)));
+    }
+
+    #[test]
+    fn predicate_contramap() {
+        let long = Predicate::new(|n: &u32| *n > 3).contramap(|s: &str| s.len() as u32);
+        assert!(long.call("category"));
+        assert!(!long.call("cat"));
+    }
+
+    #[test]
+    fn predicate_contramap_composes() {
+        let p = Predicate::new(|n: &usize| n.is_multiple_of(2))
+            .contramap(|s: &String| s.len())
+            .contramap(|v: &Vec<&str>| v.concat());
+        assert!(p.call(&vec!["ab", "cd"]));
+    }
+
+    struct User {
+        name: String,
+        age: u32,
+    }
+
+    fn valid_user() -> Predicate<User> {
+        let name = Predicate::new(|s: &str| !s.is_empty());
+        let age = Predicate::new(|n: &u32| *n >= 18);
+        name.divide(|u: &User| (u.name.as_str(), &u.age), age)
+    }
+
+    #[test]
+    fn predicate_divide() {
+        let user = |name: &str, age| User {
+            name: name.to_string(),
+            age,
+        };
+        let valid = valid_user();
+        assert!(valid.call(&user("alice", 30)));
+        assert!(!valid.call(&user("", 30)));
+        assert!(!valid.call(&user("bob", 12)));
+        assert!(Predicate::<User>::conquer().call(&user("", 0)));
+    }
+
+    enum Id {
+        Number(u32),
+        Name(String),
+    }
+
+    fn valid_id() -> Predicate<Id> {
+        let number = Predicate::new(|n: &u32| *n > 0);
+        let name = Predicate::new(|s: &String| s.len() > 2);
+        number.choose(
+            |id: &Id| match id {
+                Id::Number(n) => Either::Left(n),
+                Id::Name(s) => Either::Right(s),
+            },
+            name,
+        )
+    }
+
+    #[test]
+    fn predicate_choose() {
+        let valid = valid_id();
+        assert!(valid.call(&Id::Number(3)));
+        assert!(!valid.call(&Id::Number(0)));
+        assert!(valid.call(&Id::Name("root".into())));
+        assert!(!valid.call(&Id::Name("me".into())));
+    }
+
+    #[test]
+    fn const_ignores_values() {
+        let a: Const<Sum<i32>, String> = Const::new(Sum(2));
+        let b = Const::new(Sum(5));
+        assert_eq!(a.clone().op(b).into_inner(), Sum(7));
+        assert_eq!(Const::<Sum<i32>, String>::empty().into_inner(), Sum(0));
+
+        let mapped: Const<Sum<i32>, usize> = a.fmap(|s: String| s.len());
+        assert_eq!(mapped.into_inner(), Sum(2));
+        let pure = <Const<Sum<i32>, String> as Applicative<usize>>::pure(String::from("x"));
+        assert_eq!(pure.into_inner(), Sum(0));
+        let f = Const::<_, fn(String) -> usize>::new(Sum(1));
+        assert_eq!(Const::new(Sum(4)).apply(f).into_inner(), Sum(5));
+    }
+
+    #[test]
+    fn identity_monad() {
+        assert_eq!(Identity(3).bind(|x| Identity(x + 1)), Identity(4));
+        let h = |x: i32| Identity(x * 10);
+        let k = |x: i32| Identity(x - 3);
+        assert_eq!(<Identity<i32> as Applicative<i32>>::pure(5).bind(h), h(5));
+        assert_eq!(
+            Identity(5).bind(<Identity<i32> as Applicative<i32>>::pure),
+            Identity(5)
+        );
+        assert_eq!(
+            Identity(5).bind(h).bind(k),
+            Identity(5).bind(|x| h(x).bind(k))
+        );
+        assert_eq!(Identity(2).apply(Identity(g)).fmap(f), Identity(5));
+        assert_eq!(Identity(Sum(1)).op(Identity::empty()), Identity(Sum(1)));
+    }
+
+    #[test]
+    fn option_alternative() {
+        assert_eq!(None.alt(Some(1)), Some(1));
+        assert_eq!(Some(2).alt(Some(1)), Some(2));
+        assert_eq!(Some(3).alt(Option::empty_alt()), Some(3));
+        // Unlike the `Option` monoid, which combines both values
+        assert_eq!(Some(Sum(2)).op(Some(Sum(1))), Some(Sum(3)));
+        assert_eq!(Some(Sum(2)).alt(Some(Sum(1))), Some(Sum(2)));
+    }
+
+    #[test]
+    fn vec_alternative() {
+        assert_eq!(vec![1, 2].alt(vec![3]), vec![1, 2, 3]);
+        assert_eq!(Vec::<i32>::empty_alt().alt(vec![4]), vec![4]);
+        assert!(Vec::<i32>::empty_alt().is_empty());
+    }
+
+    #[test]
+    fn option_product() {
+        assert_eq!(Some(1).product(Some("a")), Some((1, "a")));
+        assert_eq!(Some(1).product(None::<&str>), None);
+        assert_eq!(None::<i32>.product(Some("a")), None);
+    }
+
+    #[test]
+    fn result_vec_product() {
+        let ok: Result<i32, &str> = Ok(1);
+        assert_eq!(ok.product(Ok('x')), Ok((1, 'x')));
+        assert_eq!(
+            Err::<i32, _>("first").product(Err::<char, _>("second")),
+            Err("first")
+        );
+        assert_eq!(ok.product(Err::<char, _>("second")), Err("second"));
+        assert_eq!(
+            vec![1, 2].product(vec!['a', 'b']),
+            vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]
+        );
+    }
+
+    #[test]
+    fn option_to_vec() {
+        assert!(OptionToVec::transform(None::<i32>).is_empty());
+        assert_eq!(OptionToVec::transform(Some(3)), vec![3]);
+        for x in [None, Some(3)] {
+            assert_eq!(
+                OptionToVec::transform(x.fmap(f)),
+                OptionToVec::transform(x).fmap(f)
+            );
+        }
+    }
+
+    #[test]
+    fn result_to_option() {
+        type T = ResultToOption<&'static str>;
+        assert_eq!(T::transform(Ok(2)), Some(2));
+        assert_eq!(T::transform(Err::<i32, _>("no")), None);
+        for x in [Ok(2), Err("no")] {
+            assert_eq!(T::transform(x.map(g)), T::transform(x).fmap(g));
+        }
+    }
+
+    #[test]
+    fn function_dimap() {
+        let inc = |x: i32| x + 1;
+        let h = inc.dimap(|s: &str| s.len() as i32, |x| format!("<{}>", x));
+        assert_eq!(h("abc"), "<4>");
+        assert_eq!(inc.lmap(|x: u8| x as i32)(2), 3);
+        assert_eq!(inc.rmap(|x| x * 2)(2), 6);
+
+        let boxed: Box<dyn Fn(i32) -> i32> = Box::new(g);
+        let h = boxed.dimap(f, f);
+        assert_eq!(h(1), 5);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::traits::*;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::Range;
+
+/// A set of values given as half-open ranges, combining by union. The ranges are kept sorted,
+/// disjoint and non-empty, with overlapping or adjacent ranges merged into one, so that equal
+/// sets have the same ranges.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T: Ord>(Vec<Range<T>>);
+
+impl<T: Ord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet(Vec::new())
+    }
+}
+
+impl<T: Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges ranges sorted by their start.
+    fn merge_sorted(sorted: impl Iterator<Item = Range<T>>) -> Self {
+        let mut res: Vec<Range<T>> = Vec::new();
+        for range in sorted.filter(|r| !r.is_empty()) {
+            match res.last_mut() {
+                Some(last) if last.end >= range.start => {
+                    if range.end > last.end {
+                        last.end = range.end;
+                    }
+                }
+                _ => res.push(range),
+            }
+        }
+        IntervalSet(res)
+    }
+
+    pub fn contains(&self, point: &T) -> bool {
+        // Index of the first range starting after `point`; only the one before may contain it
+        let i = self.0.partition_point(|r| r.start <= *point);
+        i > 0 && self.0[i - 1].end > *point
+    }
+
+    /// Returns the ranges of the set, sorted and disjoint.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.0
+    }
+}
+
+impl<T: Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::merge_sorted(core::iter::once(range))
+    }
+}
+
+impl<T: Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<_> = iter.into_iter().collect();
+        ranges.sort_by(|a, b| a.start.cmp(&b.start));
+        Self::merge_sorted(ranges.into_iter())
+    }
+}
+
+/// Sweeps through the ranges of both sets in order of their start, in linear time.
+impl<T: Ord> Semigroup for IntervalSet<T> {
+    fn op(self, other: Self) -> Self {
+        let mut a = self.0.into_iter().peekable();
+        let mut b = other.0.into_iter().peekable();
+        let sorted = core::iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if y.start < x.start => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        });
+        Self::merge_sorted(sorted)
+    }
+}
+
+impl<T: Ord> DefaultMonoid for IntervalSet<T> {}
+impl<T: Ord> CommutativeMonoid for IntervalSet<T> {}
+impl<T: Ord> Band for IntervalSet<T> {}
+impl<T: Ord> Semilattice for IntervalSet<T> {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::interval::IntervalSet;
+    use crate::traits::*;
+
+    #[test]
+    fn interval_set_merges_overlaps() {
+        let a: IntervalSet<_> = vec![1..3, 5..7].into_iter().collect();
+        let b = IntervalSet::from(2..6);
+        assert_eq!(a.clone().op(b.clone()), IntervalSet::from(1..7));
+        assert_eq!(b.op(a.clone()), IntervalSet::from(1..7));
+        assert_eq!(a.clone().op(IntervalSet::empty()), a);
+    }
+
+    #[test]
+    fn interval_set_keeps_gaps() {
+        let chunks = vec![10..20, 0..4, 4..6, 30..30, 15..25, 8..9];
+        let set: IntervalSet<_> = Monoid::concat(chunks.into_iter().map(IntervalSet::from));
+        assert_eq!(set.ranges(), &[0..6, 8..9, 10..25]);
+        assert!(set.contains(&0));
+        assert!(set.contains(&5));
+        assert!(!set.contains(&6));
+        assert!(!set.contains(&9));
+        assert!(set.contains(&24));
+        assert!(!set.contains(&25));
+        assert!(!IntervalSet::new().contains(&0));
+    }
+}
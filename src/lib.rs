@@ -1,19 +1,62 @@
+//! The core traits and the non-allocating wrappers are available without the standard library,
+//! by disabling the default `std` feature. The `alloc` feature then brings back the instances
+//! for `Vec`, `String`, `Box` and other types which only need an allocator.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use crate::traits::Lattice;
 use crate::traits::*;
 use alga::general::*;
-use num_traits::{One, Zero};
-use std::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+use core::fmt;
 use num_traits::real::Real;
+use num_traits::{One, Zero};
+
+// Lets the paths generated by the derive macros resolve within this crate as well
+extern crate self as category;
 
+pub mod crdt;
+pub mod either;
+pub mod foldable;
+#[cfg(feature = "alloc")]
+pub mod free;
+pub mod functor;
+#[cfg(feature = "alloc")]
+pub mod interval;
+pub mod morphism;
+#[cfg(feature = "alloc")]
+pub mod nonempty;
+#[cfg(feature = "rayon")]
+pub mod par;
+#[cfg(feature = "alloc")]
+pub mod permutation;
+pub mod semiring;
+pub mod stats;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod traits;
+pub mod traversable;
+pub mod validation;
+pub mod writer;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Any(pub bool);
+/// Floats with a total order, for `Min` and `Max`. `OrderedFloat` orders NaN above every other
+/// value, including infinity, while `NotNan` rejects NaN on construction.
+#[cfg(feature = "ordered-float")]
+pub use ordered_float::{NotNan, OrderedFloat};
 
-impl Default for Any {
-    fn default() -> Self {
-        Self(false)
-    }
-}
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Any(pub bool);
 
 impl Semigroup for Any {
     fn op(self, other: Self) -> Self {
@@ -21,8 +64,36 @@ impl Semigroup for Any {
     }
 }
 impl DefaultMonoid for Any {}
+impl CommutativeMonoid for Any {}
+
+impl Band for Any {
+    fn is_absorbing(&self) -> bool {
+        self.0
+    }
+}
+
+impl Semilattice for Any {}
+
+impl core::ops::BitOr for Any {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.op(other)
+    }
+}
+
+impl core::ops::BitOrAssign for Any {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.op(other);
+    }
+}
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct All(pub bool);
 
 impl Default for All {
@@ -37,8 +108,60 @@ impl Semigroup for All {
     }
 }
 impl DefaultMonoid for All {}
+impl CommutativeMonoid for All {}
+
+impl Band for All {
+    fn is_absorbing(&self) -> bool {
+        !self.0
+    }
+}
+
+impl Semilattice for All {}
+
+impl core::ops::BitAnd for All {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.op(other)
+    }
+}
+
+impl core::ops::BitAndAssign for All {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = self.op(other);
+    }
+}
+
+/// Combines booleans with exclusive or, computing the parity of the number of `true` values.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Xor(pub bool);
+
+impl Semigroup for Xor {
+    fn op(self, other: Self) -> Self {
+        Xor(self.0 ^ other.0)
+    }
+}
+impl DefaultMonoid for Xor {}
+impl CommutativeMonoid for Xor {}
+
+impl Group for Xor {
+    fn inverse(self) -> Self {
+        self
+    }
+}
 
+/// Keeps the smallest value. The identity is the greatest value of `T`, its
+/// [`BoundedLattice::top`].
+///
+/// Floats aren't `Ord`, but can be wrapped in `OrderedFloat` or `NotNan` with the
+/// `ordered-float` feature. Their identities are infinite, or NaN for `Min<OrderedFloat<_>>`
+/// since NaN is ordered above infinity.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Min<T>(pub T);
 
 impl<T: Ord> Semigroup for Min<T> {
@@ -47,15 +170,25 @@ impl<T: Ord> Semigroup for Min<T> {
     }
 }
 
-impl<T: Default> Default for Min<T> {
+impl<T: BoundedLattice> Default for Min<T> {
     fn default() -> Self {
-        Self(T::default())
+        Self(T::top())
     }
 }
 
-impl<T: Ord + Default> DefaultMonoid for Min<T> {}
+impl<T: Ord + BoundedLattice> DefaultMonoid for Min<T> {}
+impl<T: Ord + BoundedLattice> CommutativeMonoid for Min<T> {}
+impl<T: Ord> Band for Min<T> {}
+impl<T: Ord + BoundedLattice> Semilattice for Min<T> {}
 
+/// Keeps the largest value. The identity is the least value of `T`, its
+/// [`BoundedLattice::bottom`]. See [`Min`] for floats.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Max<T>(pub T);
 
 impl<T: Ord> Semigroup for Max<T> {
@@ -64,15 +197,190 @@ impl<T: Ord> Semigroup for Max<T> {
     }
 }
 
-impl<T: Default> Default for Max<T> {
+impl<T: BoundedLattice> Default for Max<T> {
     fn default() -> Self {
-        Self(T::default())
+        Self(T::bottom())
+    }
+}
+
+impl<T: Ord + BoundedLattice> DefaultMonoid for Max<T> {}
+impl<T: Ord + BoundedLattice> CommutativeMonoid for Max<T> {}
+impl<T: Ord> Band for Max<T> {}
+impl<T: Ord + BoundedLattice> Semilattice for Max<T> {}
+
+/// Keeps the value with the smallest key, without requiring the value itself to be ordered.
+/// When keys are equal, the first value is kept.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MinBy<T, K> {
+    value: T,
+    key: K,
+}
+
+impl<T, K> MinBy<T, K> {
+    pub fn new(value: T, key: K) -> Self {
+        Self { value, key }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, K: Ord> Semigroup for MinBy<T, K> {
+    fn op(self, other: Self) -> Self {
+        if other.key < self.key {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<T, K: Ord> Band for MinBy<T, K> {}
+
+/// Keeps the value with the largest key, without requiring the value itself to be ordered.
+/// When keys are equal, the first value is kept.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MaxBy<T, K> {
+    value: T,
+    key: K,
+}
+
+impl<T, K> MaxBy<T, K> {
+    pub fn new(value: T, key: K) -> Self {
+        Self { value, key }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, K: Ord> Semigroup for MaxBy<T, K> {
+    fn op(self, other: Self) -> Self {
+        if other.key > self.key {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<T, K: Ord> Band for MaxBy<T, K> {}
+
+/// Keeps the payload paired with the smallest key. On ties, the first pair is kept.
+///
+/// Keys must be totally ordered, since incomparable keys such as NaN would make the operation
+/// non-associative; float keys can be wrapped in `OrderedFloat` or `NotNan`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ArgMin<K, T>(pub K, pub T);
+
+impl<K, T> ArgMin<K, T> {
+    pub fn into_inner(self) -> T {
+        self.1
+    }
+}
+
+impl<K, T> From<(K, T)> for ArgMin<K, T> {
+    fn from((key, payload): (K, T)) -> Self {
+        ArgMin(key, payload)
+    }
+}
+
+impl<K: Ord, T> Semigroup for ArgMin<K, T> {
+    fn op(self, other: Self) -> Self {
+        if other.0 < self.0 {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<K: Ord, T> Band for ArgMin<K, T> {}
+
+/// Keeps the payload paired with the largest key, with the same tie-breaking as `ArgMin`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ArgMax<K, T>(pub K, pub T);
+
+impl<K, T> ArgMax<K, T> {
+    pub fn into_inner(self) -> T {
+        self.1
+    }
+}
+
+impl<K, T> From<(K, T)> for ArgMax<K, T> {
+    fn from((key, payload): (K, T)) -> Self {
+        ArgMax(key, payload)
+    }
+}
+
+impl<K: Ord, T> Semigroup for ArgMax<K, T> {
+    fn op(self, other: Self) -> Self {
+        if other.0 > self.0 {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<K: Ord, T> Band for ArgMax<K, T> {}
+
+/// Combines values with their least upper bound, generalizing `Max` to partial orders.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Join<T>(pub T);
+
+impl<T: Lattice> Semigroup for Join<T> {
+    fn op(self, other: Self) -> Self {
+        Join(self.0.join(other.0))
+    }
+}
+
+impl<T: BoundedLattice> Monoid for Join<T> {
+    fn empty() -> Self {
+        Join(T::bottom())
+    }
+}
+
+impl<T: BoundedLattice> CommutativeMonoid for Join<T> {}
+impl<T: Lattice> Band for Join<T> {}
+impl<T: BoundedLattice> Semilattice for Join<T> {}
+
+/// Combines values with their greatest lower bound, generalizing `Min` to partial orders.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Meet<T>(pub T);
+
+impl<T: Lattice> Semigroup for Meet<T> {
+    fn op(self, other: Self) -> Self {
+        Meet(self.0.meet(other.0))
+    }
+}
+
+impl<T: BoundedLattice> Monoid for Meet<T> {
+    fn empty() -> Self {
+        Meet(T::top())
     }
 }
 
-impl<T: Ord + Default> DefaultMonoid for Max<T> {}
+impl<T: BoundedLattice> CommutativeMonoid for Meet<T> {}
+impl<T: Lattice> Band for Meet<T> {}
+impl<T: BoundedLattice> Semilattice for Meet<T> {}
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Sum<T>(pub T);
 
 impl<T: Zero> Default for Sum<T> {
@@ -87,8 +395,34 @@ impl<T: ClosedAdd> Semigroup for Sum<T> {
     }
 }
 impl<T: ClosedAdd + Zero> DefaultMonoid for Sum<T> {}
+impl<T: ClosedAdd + Zero> CommutativeMonoid for Sum<T> {}
+
+impl<T: ClosedAdd + ClosedNeg + Zero> Group for Sum<T> {
+    fn inverse(self) -> Self {
+        Sum(-self.0)
+    }
+}
+
+impl<T: ClosedAdd> core::ops::Add for Sum<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.op(other)
+    }
+}
+
+impl<T: ClosedAdd> core::ops::AddAssign for Sum<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Product<T>(pub T);
 
 impl<T: One> Default for Product<T> {
@@ -104,127 +438,1968 @@ impl<T: ClosedMul> Semigroup for Product<T> {
 }
 
 impl<T: ClosedMul + One> DefaultMonoid for Product<T> {}
+impl<T: ClosedMul + One> CommutativeMonoid for Product<T> {}
 
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
-pub struct First<T>(T);
+impl<T: ClosedMul + Real> Group for Product<T> {
+    fn inverse(self) -> Self {
+        Product(self.0.recip())
+    }
+}
 
-impl<T> Semigroup for First<T> {
-    fn op(self, other: Self) -> Self {
-        self
+impl<T: ClosedMul> core::ops::Mul for Product<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.op(other)
     }
 }
 
-impl<T: Monoid> Monoid for First<T> {
-    fn empty() -> Self {
-        First(T::empty())
+impl<T: ClosedMul> core::ops::MulAssign for Product<T> {
+    fn mul_assign(&mut self, other: Self) {
+        self.0 *= other.0;
     }
 }
 
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
-pub struct Last<T>(T);
+/// Adds integers, saturating at the maximum value instead of overflowing.
+///
+/// Only unsigned integers are supported, as saturating addition isn't associative once negative
+/// values are involved: with `i8`, `(100 + 100) + -100` is `27` but `100 + (100 + -100)` is `100`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SatSum<T>(pub T);
 
-impl<T> Semigroup for Last<T> {
+impl<T: Zero> Default for SatSum<T> {
+    fn default() -> Self {
+        SatSum(T::zero())
+    }
+}
+
+impl<T: num_traits::SaturatingAdd + num_traits::Unsigned> Semigroup for SatSum<T> {
     fn op(self, other: Self) -> Self {
-        other
+        SatSum(self.0.saturating_add(&other.0))
     }
 }
 
-impl<T: Monoid> Monoid for Last<T> {
-    fn empty() -> Self {
-        Last(T::empty())
+impl<T: num_traits::SaturatingAdd + num_traits::Unsigned> DefaultMonoid for SatSum<T> {}
+impl<T: num_traits::SaturatingAdd + num_traits::Unsigned> CommutativeMonoid for SatSum<T> {}
+
+/// Adds integers with wrapping arithmetic, so the total is taken modulo `2^n` for an `n`-bit `T`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct WrapSum<T>(pub T);
+
+impl<T: Zero> Default for WrapSum<T> {
+    fn default() -> Self {
+        WrapSum(T::zero())
+    }
+}
+
+impl<T: num_traits::WrappingAdd> Semigroup for WrapSum<T> {
+    fn op(self, other: Self) -> Self {
+        WrapSum(self.0.wrapping_add(&other.0))
     }
 }
 
-pub struct Predicate<T: ?Sized>(pub Box<dyn FnOnce(&T) -> bool>);
+impl<T: num_traits::WrappingAdd + Zero> DefaultMonoid for WrapSum<T> {}
+impl<T: num_traits::WrappingAdd + Zero> CommutativeMonoid for WrapSum<T> {}
 
-impl<T: 'static + ?Sized> Semigroup for Predicate<T> {
+impl<T: num_traits::WrappingAdd + num_traits::WrappingNeg + Zero> Group for WrapSum<T> {
+    fn inverse(self) -> Self {
+        WrapSum(self.0.wrapping_neg())
+    }
+}
+
+/// Multiplies integers with wrapping arithmetic, so the product is taken modulo `2^n` for an
+/// `n`-bit `T`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct WrapProd<T>(pub T);
+
+impl<T: One> Default for WrapProd<T> {
+    fn default() -> Self {
+        WrapProd(T::one())
+    }
+}
+
+impl<T: num_traits::WrappingMul> Semigroup for WrapProd<T> {
     fn op(self, other: Self) -> Self {
-        match (self, other) {
-            (Self(p), Self(q)) => Self::new(|a| p(a) && q(a)),
-        }
+        WrapProd(self.0.wrapping_mul(&other.0))
     }
 }
 
-impl<T: 'static> Monoid for Predicate<T> {
-    fn empty() -> Self {
-        Self::new(|_| true)
+impl<T: num_traits::WrappingMul + One> DefaultMonoid for WrapProd<T> {}
+impl<T: num_traits::WrappingMul + One> CommutativeMonoid for WrapProd<T> {}
+
+/// Adds integers modulo `M`. The sum is reduced after every `op`, going through `u128` so that it
+/// never overflows.
+///
+/// A modulus of `0` is rejected at compile time:
+///
+/// ```compile_fail
+/// # use category::ModSum;
+/// let x = ModSum::<0>::new(3);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ModSum<const M: u64>(pub u64);
+
+impl<const M: u64> ModSum<M> {
+    const NONZERO: () = assert!(M > 0, "the modulus must not be 0");
+
+    /// Reduces `x` modulo `M`.
+    pub fn new(x: u64) -> Self {
+        let () = Self::NONZERO;
+        ModSum(x % M)
     }
 }
 
-impl<T: ?Sized> Predicate<T> {
-    pub fn new<F: 'static + FnOnce(&T) -> bool>(pred: F) -> Self {
-        Self(Box::new(pred))
+impl<const M: u64> Default for ModSum<M> {
+    fn default() -> Self {
+        let () = Self::NONZERO;
+        ModSum(0)
     }
+}
 
-    pub fn call(self, x: &T) -> bool {
-        self.0(x)
+impl<const M: u64> Semigroup for ModSum<M> {
+    fn op(self, other: Self) -> Self {
+        let () = Self::NONZERO;
+        ModSum(((self.0 as u128 + other.0 as u128) % M as u128) as u64)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::traits::*;
-    use crate::*;
+impl<const M: u64> DefaultMonoid for ModSum<M> {}
+impl<const M: u64> CommutativeMonoid for ModSum<M> {}
 
-    #[test]
-    fn any_semigroup() {
-        let v = vec![0, 1, 2, 3, 4].into_iter().map(|x| x > 2).map(Any);
-        let r = Semigroup::concat(v);
-        assert_eq!(Some(Any(true)), r);
+impl<const M: u64> Group for ModSum<M> {
+    fn inverse(self) -> Self {
+        let () = Self::NONZERO;
+        ModSum((M - self.0 % M) % M)
+    }
+}
+
+/// Multiplies integers modulo `M`. The product is reduced after every `op`, going through `u128`
+/// so that it never overflows. The identity is `1 % M`.
+///
+/// A modulus of `0` is rejected at compile time:
+///
+/// ```compile_fail
+/// # use category::{traits::Semigroup, ModProduct};
+/// let x = ModProduct::<0>(2).op(ModProduct(3));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ModProduct<const M: u64>(pub u64);
+
+impl<const M: u64> ModProduct<M> {
+    const NONZERO: () = assert!(M > 0, "the modulus must not be 0");
+
+    /// Reduces `x` modulo `M`.
+    pub fn new(x: u64) -> Self {
+        let () = Self::NONZERO;
+        ModProduct(x % M)
     }
+}
 
-    #[test]
-    fn all_semigroup() {
-        let v = vec![0, 1, 2, 3, 4].into_iter().map(|x| x > 2).map(All);
-        let r = Semigroup::concat(v);
-        assert_eq!(Some(All(false)), r);
+impl<const M: u64> Default for ModProduct<M> {
+    fn default() -> Self {
+        Self::new(1)
     }
+}
 
-    #[test]
-    fn predicate_semigroup() {
-        let v = vec![
-            Predicate::new(|x: &str| x.starts_with("a")),
-            Predicate::new(|x: &str| x.ends_with("z")),
-        ];
-        let r: Option<Predicate<_>> = Semigroup::concat(v);
-        assert!(r.is_some());
-        let r = r.unwrap();
-        assert!(r.call("a to z"));
+impl<const M: u64> Semigroup for ModProduct<M> {
+    fn op(self, other: Self) -> Self {
+        let () = Self::NONZERO;
+        ModProduct(((self.0 as u128 * other.0 as u128) % M as u128) as u64)
     }
+}
 
-    #[test]
-    fn sum_semigroup() {
-        let s = Sum(0.0).op(Sum(0.1)).op(Sum(1.0)).0;
-        assert_eq!(1.1, s);
+impl<const M: u64> DefaultMonoid for ModProduct<M> {}
+impl<const M: u64> CommutativeMonoid for ModProduct<M> {}
+
+/// Adds integers, collapsing to `None` on the first overflow. Once `None`, the sum stays `None`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CheckedSum<T>(pub Option<T>);
+
+impl<T> From<T> for CheckedSum<T> {
+    fn from(x: T) -> Self {
+        CheckedSum(Some(x))
     }
+}
 
-    #[test]
-    fn product_semigroup() {
-        let v = vec![1u8, 2, 3, 4, 0, 5].into_iter().map(Product);
-        let v = Semigroup::concat(v);
-        assert!(v.is_some());
-        assert_eq!(v.unwrap(), Product(0));
+impl<T: Zero> Default for CheckedSum<T> {
+    fn default() -> Self {
+        CheckedSum(Some(T::zero()))
     }
+}
 
-    #[test]
-    fn tuple_semigroup() {
-        let a = (Sum(1.0), Product(10));
-        let b = a.op((Sum(1.0), Product(2)));
-        assert_eq!(b, (Sum(2.0), Product(20)));
+impl<T: num_traits::CheckedAdd> Semigroup for CheckedSum<T> {
+    fn op(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (Some(a), Some(b)) => CheckedSum(a.checked_add(&b)),
+            _ => CheckedSum(None),
+        }
     }
+}
 
-    #[test]
-    fn float_monoid() {
-        let v = (0u8..10).map(|x| Sum(x as f32 / 10.0));
-        let Sum(r) = Monoid::concat(v);
-        assert_eq!(r, 4.5);
+impl<T: num_traits::CheckedAdd + Zero> DefaultMonoid for CheckedSum<T> {}
+
+/// Counts elements. This is `Sum<u64>` under a name which documents intent, and which can't be
+/// mixed up with an unrelated sum.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Count(pub u64);
+
+impl Count {
+    /// Counts any value as one, so that `items.fold_map(Count::one)` counts the items.
+    pub fn one<T>(_: T) -> Self {
+        Count(1)
     }
+}
 
-    #[test]
-    fn int_min_monoid() {
-        let v = vec![1i32,-1,15,-42,74,42].into_iter().map(Min);
-        let Min(r) = Monoid::concat(v);
+impl Semigroup for Count {
+    fn op(self, other: Self) -> Self {
+        Count(self.0 + other.0)
+    }
+}
+impl DefaultMonoid for Count {}
+impl CommutativeMonoid for Count {}
 
-        assert_eq!(r, -42);
+/// Combines integers with their greatest common divisor. The identity is zero, since
+/// `gcd(0, x) == x`.
+///
+/// The divisor is never negative, so for signed integers `Gcd(0).op(Gcd(-4)) == Gcd(4)` and zero
+/// isn't an identity: only the `Semigroup` is implemented for them, and the `Monoid` is limited
+/// to unsigned integers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Gcd<T>(pub T);
+
+impl<T: Zero> Default for Gcd<T> {
+    fn default() -> Self {
+        Gcd(T::zero())
+    }
+}
+
+impl<T: num_integer::Integer> Semigroup for Gcd<T> {
+    fn op(self, other: Self) -> Self {
+        Gcd(self.0.gcd(&other.0))
+    }
+}
+
+impl<T: num_integer::Integer + num_traits::Unsigned> DefaultMonoid for Gcd<T> {}
+impl<T: num_integer::Integer + num_traits::Unsigned> CommutativeMonoid for Gcd<T> {}
+
+/// Combines integers with their least common multiple. The identity is one.
+///
+/// Like [`Gcd`], the multiple is never negative, so `Lcm(1).op(Lcm(-4)) == Lcm(4)` and the
+/// `Monoid` is limited to unsigned integers.
+///
+/// The multiple is computed as `a * (b / gcd(a, b))`, which only overflows when the result itself
+/// doesn't fit in `T`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Lcm<T>(pub T);
+
+impl<T: One> Default for Lcm<T> {
+    fn default() -> Self {
+        Lcm(T::one())
+    }
+}
+
+impl<T: num_integer::Integer> Semigroup for Lcm<T> {
+    fn op(self, other: Self) -> Self {
+        Lcm(self.0.lcm(&other.0))
+    }
+}
+
+impl<T: num_integer::Integer + num_traits::Unsigned> DefaultMonoid for Lcm<T> {}
+impl<T: num_integer::Integer + num_traits::Unsigned> CommutativeMonoid for Lcm<T> {}
+
+/// Combines integers with bitwise and. The identity has all bits set.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BitAnd<T>(pub T);
+
+impl<T: num_traits::PrimInt> Default for BitAnd<T> {
+    fn default() -> Self {
+        BitAnd(!T::zero())
+    }
+}
+
+impl<T: core::ops::BitAnd<Output = T>> Semigroup for BitAnd<T> {
+    fn op(self, other: Self) -> Self {
+        BitAnd(self.0 & other.0)
+    }
+}
+
+impl<T: num_traits::PrimInt> DefaultMonoid for BitAnd<T> {}
+impl<T: num_traits::PrimInt> CommutativeMonoid for BitAnd<T> {}
+impl<T: core::ops::BitAnd<Output = T>> Band for BitAnd<T> {}
+impl<T: num_traits::PrimInt> Semilattice for BitAnd<T> {}
+
+/// Combines integers with bitwise or. The identity is zero.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BitOr<T>(pub T);
+
+impl<T: Zero> Default for BitOr<T> {
+    fn default() -> Self {
+        BitOr(T::zero())
+    }
+}
+
+impl<T: core::ops::BitOr<Output = T>> Semigroup for BitOr<T> {
+    fn op(self, other: Self) -> Self {
+        BitOr(self.0 | other.0)
+    }
+}
+
+impl<T: num_traits::PrimInt> DefaultMonoid for BitOr<T> {}
+impl<T: num_traits::PrimInt> CommutativeMonoid for BitOr<T> {}
+impl<T: core::ops::BitOr<Output = T>> Band for BitOr<T> {}
+impl<T: num_traits::PrimInt> Semilattice for BitOr<T> {}
+
+/// Combines integers with bitwise exclusive or. The identity is zero, and every value is its own
+/// inverse.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BitXor<T>(pub T);
+
+impl<T: Zero> Default for BitXor<T> {
+    fn default() -> Self {
+        BitXor(T::zero())
+    }
+}
+
+impl<T: core::ops::BitXor<Output = T>> Semigroup for BitXor<T> {
+    fn op(self, other: Self) -> Self {
+        BitXor(self.0 ^ other.0)
+    }
+}
+
+impl<T: num_traits::PrimInt> DefaultMonoid for BitXor<T> {}
+impl<T: num_traits::PrimInt> CommutativeMonoid for BitXor<T> {}
+
+impl<T: num_traits::PrimInt> Group for BitXor<T> {
+    fn inverse(self) -> Self {
+        self
+    }
+}
+
+/// Keeps the first value present, the identity being the absence of a value.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct First<T>(pub Option<T>);
+
+impl<T> Default for First<T> {
+    fn default() -> Self {
+        First(None)
+    }
+}
+
+impl<T> From<T> for First<T> {
+    fn from(x: T) -> Self {
+        First(Some(x))
+    }
+}
+
+impl<T> Semigroup for First<T> {
+    fn op(self, other: Self) -> Self {
+        First(self.0.or(other.0))
+    }
+}
+impl<T> DefaultMonoid for First<T> {}
+impl<T> Band for First<T> {}
+
+/// Keeps the last value present, the identity being the absence of a value.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Last<T>(pub Option<T>);
+
+impl<T> Default for Last<T> {
+    fn default() -> Self {
+        Last(None)
+    }
+}
+
+impl<T> From<T> for Last<T> {
+    fn from(x: T) -> Self {
+        Last(Some(x))
+    }
+}
+
+impl<T> Semigroup for Last<T> {
+    fn op(self, other: Self) -> Self {
+        Last(other.0.or(self.0))
+    }
+}
+impl<T> DefaultMonoid for Last<T> {}
+impl<T> Band for Last<T> {}
+
+macro_rules! impl_wrapper_helpers {
+    ($($name:ident)*) => {$(
+        impl<T> $name<T> {
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+
+            /// Applies `f` to the wrapped value.
+            pub fn map<F: FnOnce(T) -> T>(self, f: F) -> Self {
+                $name(f(self.0))
+            }
+
+            /// Applies `f` to the wrapped value, which may change its type, e.g.
+            /// `Sum(3).fmap(f64::from) == Sum(3.0)`. This is `Functor::fmap`, without having to
+            /// import the trait and with a `FnOnce`.
+            pub fn fmap<U, F: FnOnce(T) -> U>(self, f: F) -> $name<U> {
+                $name(f(self.0))
+            }
+        }
+
+        impl<T> AsRef<T> for $name<T> {
+            fn as_ref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    )*};
+}
+
+impl_wrapper_helpers!(Min Max Sum Product);
+
+macro_rules! impl_optional_wrapper_helpers {
+    ($($name:ident)*) => {$(
+        impl<T> $name<T> {
+            pub fn into_inner(self) -> Option<T> {
+                self.0
+            }
+
+            /// Applies `f` to the wrapped value, if there is one.
+            pub fn map<F: FnOnce(T) -> T>(self, f: F) -> Self {
+                $name(self.0.map(f))
+            }
+        }
+
+        impl<T> AsRef<Option<T>> for $name<T> {
+            fn as_ref(&self) -> &Option<T> {
+                &self.0
+            }
+        }
+
+        /// Displays the value, or nothing in its absence.
+        impl<T: fmt::Display> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match &self.0 {
+                    Some(x) => x.fmt(f),
+                    None => Ok(()),
+                }
+            }
+        }
+    )*};
+}
+
+impl_optional_wrapper_helpers!(First Last);
+
+macro_rules! impl_bool_wrapper_helpers {
+    ($($name:ident)*) => {$(
+        impl $name {
+            pub fn into_inner(self) -> bool {
+                self.0
+            }
+
+            /// Applies `f` to the wrapped value.
+            pub fn map<F: FnOnce(bool) -> bool>(self, f: F) -> Self {
+                $name(f(self.0))
+            }
+        }
+
+        impl AsRef<bool> for $name {
+            fn as_ref(&self) -> &bool {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    )*};
+}
+
+impl_bool_wrapper_helpers!(Any All);
+
+/// The opposite semigroup of `T`, combining elements in reverse order:
+/// `Dual(a).op(Dual(b)) == Dual(b.op(a))`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Dual<T>(pub T);
+
+impl<T: Semigroup> Semigroup for Dual<T> {
+    fn op(self, other: Self) -> Self {
+        Dual(other.0.op(self.0))
+    }
+}
+
+impl<T: Monoid> Monoid for Dual<T> {
+    fn empty() -> Self {
+        Dual(T::empty())
+    }
+}
+
+impl<T: Group> Group for Dual<T> {
+    fn inverse(self) -> Self {
+        Dual(self.0.inverse())
+    }
+}
+
+impl<T: CommutativeMonoid> CommutativeMonoid for Dual<T> {}
+impl<T: Band> Band for Dual<T> {
+    fn is_absorbing(&self) -> bool {
+        self.0.is_absorbing()
+    }
+}
+impl<T: Semilattice> Semilattice for Dual<T> {}
+
+/// A test on borrowed values, which can be evaluated any number of times. Predicates combine by
+/// conjunction, the identity accepting everything.
+#[cfg(feature = "alloc")]
+pub struct Predicate<T: ?Sized>(pub Box<dyn Fn(&T) -> bool>);
+
+#[cfg(feature = "alloc")]
+impl<T: 'static + ?Sized> Semigroup for Predicate<T> {
+    fn op(self, other: Self) -> Self {
+        match (self, other) {
+            (Self(p), Self(q)) => Self::new(move |a| p(a) && q(a)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static> Monoid for Predicate<T> {
+    fn empty() -> Self {
+        Self::new(|_| true)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Predicate<T> {
+    pub fn new<F: 'static + Fn(&T) -> bool>(pred: F) -> Self {
+        Self(Box::new(pred))
+    }
+
+    pub fn call(&self, x: &T) -> bool {
+        self.0(x)
+    }
+}
+
+/// A comparator, combining lexicographically: the second comparator only breaks the ties of the
+/// first, as in the `Ordering` semigroup. The identity considers every pair of values equal.
+///
+/// Reversing a combined comparator with [`Comparison::reverse`] reverses every comparator in the
+/// chain, like comparing `Reverse` keys would.
+#[cfg(feature = "alloc")]
+pub struct Comparison<T: ?Sized>(pub Box<Comparator<T>>);
+
+#[cfg(feature = "alloc")]
+type Comparator<T> = dyn Fn(&T, &T) -> Ordering;
+
+#[cfg(feature = "alloc")]
+impl<T: 'static + ?Sized> Semigroup for Comparison<T> {
+    fn op(self, other: Self) -> Self {
+        match (self, other) {
+            (Self(f), Self(g)) => Self::new(move |a, b| f(a, b).then_with(|| g(a, b))),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static + ?Sized> Monoid for Comparison<T> {
+    fn empty() -> Self {
+        Self::new(|_, _| Ordering::Equal)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Comparison<T> {
+    pub fn new<F: 'static + Fn(&T, &T) -> Ordering>(cmp: F) -> Self {
+        Self(Box::new(cmp))
+    }
+
+    /// Compares values by the key `f` extracts from them.
+    pub fn by_key<K: Ord, F: 'static + Fn(&T) -> K>(f: F) -> Self {
+        Self::new(move |a, b| f(a).cmp(&f(b)))
+    }
+
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0(a, b)
+    }
+
+    /// Compares values in the opposite order.
+    pub fn reverse(self) -> Self
+    where
+        T: 'static,
+    {
+        let f = self.0;
+        Self::new(move |a, b| f(a, b).reverse())
+    }
+}
+
+/// An equivalence relation, combining by conjunction: values are equivalent when every relation
+/// considers them so. The identity considers every pair of values equivalent.
+///
+/// The relations aren't checked to be reflexive, symmetric and transitive, which is up to the
+/// caller. The conjunction of equivalence relations is one as well.
+#[cfg(feature = "alloc")]
+pub struct Equivalence<T: ?Sized>(pub Box<Relation<T>>);
+
+#[cfg(feature = "alloc")]
+type Relation<T> = dyn Fn(&T, &T) -> bool;
+
+#[cfg(feature = "alloc")]
+impl<T: 'static + ?Sized> Semigroup for Equivalence<T> {
+    fn op(self, other: Self) -> Self {
+        match (self, other) {
+            (Self(f), Self(g)) => Self::new(move |a, b| f(a, b) && g(a, b)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static + ?Sized> Monoid for Equivalence<T> {
+    fn empty() -> Self {
+        Self::new(|_, _| true)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Equivalence<T> {
+    pub fn new<F: 'static + Fn(&T, &T) -> bool>(eq: F) -> Self {
+        Self(Box::new(eq))
+    }
+
+    /// Relates values whose keys, as extracted by `f`, are equal.
+    pub fn by_key<K: PartialEq, F: 'static + Fn(&T) -> K>(f: F) -> Self {
+        Self::new(move |a, b| f(a) == f(b))
+    }
+
+    pub fn call(&self, a: &T, b: &T) -> bool {
+        self.0(a, b)
+    }
+}
+
+/// Functions from a type to itself, under composition.
+///
+/// Composition goes right-to-left, like `∘`: `Endo(f).op(Endo(g))` applies `g` first, then `f`.
+/// Folding `[f, g, h]` thus gives `|x| f(g(h(x)))`.
+///
+/// Function pointers `fn(T) -> T` convert into `Endo` with `From`. They can't be a semigroup
+/// themselves, as composing two pointers needs a closure capturing both, which no function
+/// pointer can stand for.
+#[cfg(feature = "alloc")]
+pub struct Endo<T>(pub Box<dyn Fn(T) -> T>);
+
+#[cfg(feature = "alloc")]
+impl<T: 'static> Semigroup for Endo<T> {
+    fn op(self, other: Self) -> Self {
+        match (self, other) {
+            (Self(f), Self(g)) => Self::new(move |x| f(g(x))),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static> Monoid for Endo<T> {
+    fn empty() -> Self {
+        Self::new(|x| x)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Endo<T> {
+    pub fn new<F: 'static + Fn(T) -> T>(f: F) -> Self {
+        Self(Box::new(f))
+    }
+
+    pub fn call(&self, x: T) -> T {
+        self.0(x)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: 'static> From<fn(T) -> T> for Endo<T> {
+    fn from(f: fn(T) -> T) -> Self {
+        Self::new(f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::traits::Lattice;
+    use crate::traits::*;
+    use crate::*;
+    use std::borrow::Cow;
+    use std::collections::{
+        BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque,
+    };
+    use std::ffi::OsString;
+    use std::marker::PhantomData;
+    use std::num::NonZeroUsize;
+    use std::path::PathBuf;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn naive_times<T: Semigroup + Clone>(x: T, n: usize) -> T {
+        (1..n).fold(x.clone(), |acc, _| acc.op(x.clone()))
+    }
+
+    /// Counts the allocations of each thread, so tests running in parallel don't interfere.
+    struct CountingAlloc;
+
+    thread_local! {
+        static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+    /// Runs `f`, also returning how many allocations it made.
+    fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+        let before = ALLOCATIONS.with(|n| n.get());
+        let r = f();
+        (r, ALLOCATIONS.with(|n| n.get()) - before)
+    }
+
+    #[test]
+    fn any_semigroup() {
+        let v = vec![0, 1, 2, 3, 4].into_iter().map(|x| x > 2).map(Any);
+        let r = Semigroup::concat(v);
+        assert_eq!(Some(Any(true)), r);
+    }
+
+    #[test]
+    fn all_semigroup() {
+        let v = vec![0, 1, 2, 3, 4].into_iter().map(|x| x > 2).map(All);
+        let r = Semigroup::concat(v);
+        assert_eq!(Some(All(false)), r);
+    }
+
+    #[test]
+    fn predicate_semigroup() {
+        let v = vec![
+            Predicate::new(|x: &str| x.starts_with("a")),
+            Predicate::new(|x: &str| x.ends_with("z")),
+        ];
+        let r: Option<Predicate<_>> = Semigroup::concat(v);
+        assert!(r.is_some());
+        let r = r.unwrap();
+        assert!(r.call("a to z"));
+    }
+
+    #[test]
+    fn predicate_reuse() {
+        let p = Predicate::new(|x: &i32| *x > 0)
+            .op(Predicate::new(|x: &i32| x % 2 == 0))
+            .op(Predicate::empty());
+        let accepted: Vec<_> = (-4..=6).filter(|x| p.call(x)).collect();
+        assert_eq!(accepted, vec![2, 4, 6]);
+        assert!(!p.call(&3));
+    }
+
+    #[test]
+    fn sum_semigroup() {
+        let s = Sum(0.0).op(Sum(0.1)).op(Sum(1.0)).0;
+        assert_eq!(1.1, s);
+    }
+
+    #[test]
+    fn product_semigroup() {
+        let v = vec![1u8, 2, 3, 4, 0, 5].into_iter().map(Product);
+        let v = Semigroup::concat(v);
+        assert!(v.is_some());
+        assert_eq!(v.unwrap(), Product(0));
+    }
+
+    #[test]
+    fn tuple_semigroup() {
+        let a = (Sum(1.0), Product(10));
+        let b = a.op((Sum(1.0), Product(2)));
+        assert_eq!(b, (Sum(2.0), Product(20)));
+    }
+
+    #[test]
+    fn float_monoid() {
+        let v = (0u8..10).map(|x| Sum(x as f32 / 10.0));
+        let Sum(r) = Monoid::concat(v);
+        assert_eq!(r, 4.5);
+    }
+
+    #[test]
+    fn int_min_monoid() {
+        let v = vec![1i32, -1, 15, -42, 74, 42].into_iter().map(Min);
+        let Min(r) = Monoid::concat(v);
+
+        assert_eq!(r, -42);
+    }
+
+    #[test]
+    fn repeat_zero_is_identity() {
+        assert_eq!(Sum(2).repeat(0), Sum(2));
+    }
+
+    #[test]
+    fn repeat_one_combines_twice() {
+        assert_eq!(Sum(2).repeat(1), Sum(4));
+        assert_eq!(Sum(2).repeat(3), Sum(8));
+    }
+
+    #[test]
+    fn repeat_preserves_order() {
+        assert_eq!(String::from("ab").repeat(2), "ababab");
+        assert_eq!(vec![1, 2].repeat(1), vec![1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn stimes_string() {
+        let n = NonZeroUsize::new(3).unwrap();
+        assert_eq!(String::from("ab").stimes(n), "ababab");
+        for n in 1..20 {
+            let s = String::from("xyz").stimes(NonZeroUsize::new(n).unwrap());
+            assert_eq!(s, naive_times(String::from("xyz"), n));
+        }
+    }
+
+    #[test]
+    fn stimes_vec() {
+        for n in 1..20 {
+            let v = vec![1, 2, 3].stimes(NonZeroUsize::new(n).unwrap());
+            assert_eq!(v, naive_times(vec![1, 2, 3], n));
+        }
+    }
+
+    #[test]
+    fn stimes_product() {
+        for n in 1..40 {
+            let p = Product(3u64).stimes(NonZeroUsize::new(n).unwrap());
+            assert_eq!(p, naive_times(Product(3u64), n));
+        }
+    }
+
+    #[test]
+    fn mtimes_zero_is_empty() {
+        assert_eq!(String::from("ab").mtimes(0), "");
+        assert_eq!(Product(3u64).mtimes(0), Product(1));
+        assert_eq!(vec![1].mtimes(2), vec![1, 1]);
+    }
+
+    #[test]
+    fn sum_group() {
+        for x in [-3, 0, 1, 42] {
+            assert_eq!(Sum(x).op(Sum(x).inverse()), Monoid::empty());
+        }
+        assert_eq!(Sum(10).op(Sum(4)).subtract(Sum(4)), Sum(10));
+    }
+
+    #[test]
+    fn product_group() {
+        assert_eq!(Product(4.0).op(Product(4.0).inverse()), Product(1.0));
+        assert_eq!(Product(6.0).subtract(Product(2.0)), Product(3.0));
+    }
+
+    fn unordered_concat<M: CommutativeMonoid>(v: Vec<M>) -> M {
+        Monoid::concat(v.into_iter().rev())
+    }
+
+    #[test]
+    fn commutative_monoid() {
+        let v = vec![(Sum(1), Max(3)), (Sum(2), Max(5)), (Sum(3), Max(4))];
+        assert_eq!(unordered_concat(v.clone()), Monoid::concat(v));
+        assert_eq!(unordered_concat(vec![All(true), All(false)]), All(false));
+    }
+
+    #[test]
+    fn max_band() {
+        assert_eq!(Max(5).op(Max(5)), Max(5));
+        let v = vec![3, 3, 3, 5, 5, 1].into_iter().map(Max);
+        assert_eq!(Band::concat_dedup(v), Some(Max(5)));
+    }
+
+    #[test]
+    fn any_band_short_circuits() {
+        let v = std::iter::repeat_n(Any(false), 5)
+            .chain(std::iter::once(Any(true)))
+            .chain(std::iter::repeat(Any(false)));
+        assert_eq!(Band::concat_dedup(v), Some(Any(true)));
+    }
+
+    #[test]
+    fn min_max_absorption() {
+        for (a, b) in [(1, 2), (5, -3), (4, 4)] {
+            assert_eq!(a.join(a.meet(b)), a);
+            assert_eq!(a.meet(a.join(b)), a);
+            assert_eq!(Max(a).op(Max(Min(a).op(Min(b)).0)), Max(a));
+            assert_eq!(Min(a).op(Min(Max(a).op(Max(b)).0)), Min(a));
+        }
+    }
+
+    fn fixpoint<S: Semilattice + PartialEq + Clone>(start: S, step: impl Fn(&S) -> S) -> S {
+        let mut cur = start;
+        loop {
+            let next = cur.clone().op(step(&cur));
+            if next == cur {
+                return cur;
+            }
+            cur = next;
+        }
+    }
+
+    #[test]
+    fn semilattice_fixpoint() {
+        let r = fixpoint(Max(0u8), |Max(x)| Max((*x + 3).min(10)));
+        assert_eq!(r, Max(10));
+    }
+
+    #[test]
+    fn endo_semigroup() {
+        let v = vec![Endo::new(|x| x + 1), Endo::new(|x| x * 2)];
+        let r: Option<Endo<i32>> = Semigroup::concat(v);
+        assert_eq!(r.unwrap().call(3), 7);
+    }
+
+    #[test]
+    fn endo_monoid() {
+        let e: Endo<i32> = Monoid::concat(vec![]);
+        assert_eq!(e.call(3), 3);
+        let e = Endo::empty().op(Endo::new(|s: String| s + "!"));
+        assert_eq!(e.call("hi".into()), "hi!");
+    }
+
+    #[test]
+    fn dual_semigroup() {
+        let r = Dual(String::from("a")).op(Dual("b".into()));
+        assert_eq!(r, Dual("ba".to_string()));
+        let v = vec!["a", "b", "c"].into_iter().map(String::from).map(Dual);
+        let Dual(r) = Monoid::concat(v);
+        assert_eq!(r, "cba");
+    }
+
+    #[test]
+    fn dual_first_is_last() {
+        let r = Dual(First::from(1)).op(Dual(First::from(2)));
+        assert_eq!(r, Dual(First::from(2)));
+    }
+
+    #[test]
+    fn dual_dual_round_trips() {
+        let a = || Dual(Dual(vec![1, 2]));
+        let b = || Dual(Dual(vec![3]));
+        assert_eq!(a().op(b()).0 .0, vec![1, 2].op(vec![3]));
+        assert_eq!(Dual::<Dual<Vec<i32>>>::empty(), Dual(Dual(vec![])));
+    }
+
+    fn counter(v: &[(&str, u64)]) -> HashMap<String, Sum<u64>> {
+        v.iter().map(|&(k, n)| (k.to_string(), Sum(n))).collect()
+    }
+
+    #[test]
+    fn hashmap_disjoint() {
+        let r = counter(&[("a", 1)]).op(counter(&[("b", 2), ("c", 3)]));
+        assert_eq!(r, counter(&[("a", 1), ("b", 2), ("c", 3)]));
+    }
+
+    #[test]
+    fn hashmap_overlapping() {
+        let r = counter(&[("a", 1), ("b", 2)]).op(counter(&[("b", 3)]));
+        assert_eq!(r, counter(&[("a", 1), ("b", 5)]));
+    }
+
+    #[test]
+    fn hashmap_keeps_value_order() {
+        let small: HashMap<_, _> = vec![(1, String::from("a"))].into_iter().collect();
+        let large: HashMap<_, _> = vec![(1, String::from("b")), (2, String::from("c"))]
+            .into_iter()
+            .collect();
+        assert_eq!(small.clone().op(large.clone())[&1], "ab");
+        assert_eq!(large.op(small)[&1], "ba");
+    }
+
+    #[test]
+    fn hashmap_empty() {
+        let m = counter(&[("a", 1)]);
+        assert_eq!(HashMap::empty().op(m.clone()), m);
+        assert_eq!(m.clone().op(HashMap::empty()), m);
+    }
+
+    fn tags(v: &[&'static str]) -> HashSet<&'static str> {
+        v.iter().copied().collect()
+    }
+
+    #[test]
+    fn hashset_union() {
+        let v = vec![tags(&["a", "b"]), tags(&["b", "c"]), tags(&["a", "d"])];
+        let r = Monoid::concat(v);
+        assert_eq!(r, tags(&["a", "b", "c", "d"]));
+        assert_eq!(r.len(), 4);
+    }
+
+    #[test]
+    fn hashset_semilattice() {
+        let a = tags(&["a", "b"]);
+        assert_eq!(a.clone().op(a.clone()), a);
+        assert_eq!(HashSet::empty().op(a.clone()), a);
+        assert_eq!(
+            unordered_concat(vec![a.clone(), tags(&["c"])]),
+            tags(&["a", "b", "c"])
+        );
+    }
+
+    #[test]
+    fn btreemap_merge() {
+        let a: BTreeMap<_, _> = vec![(1, Sum(1)), (2, Sum(2))].into_iter().collect();
+        let b: BTreeMap<_, _> = vec![(2, Sum(3))].into_iter().collect();
+        let r = a.op(b);
+        assert_eq!(
+            r.into_iter().collect::<Vec<_>>(),
+            vec![(1, Sum(1)), (2, Sum(5))]
+        );
+    }
+
+    #[test]
+    fn btreemap_sorted_output() {
+        let v = vec![(3, "c"), (1, "a"), (2, "b"), (1, "d")]
+            .into_iter()
+            .map(|(k, v)| {
+                vec![(k, String::from(v))]
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>()
+            });
+        let r = Monoid::concat(v);
+        let keys: Vec<_> = r.keys().copied().collect();
+        assert_eq!(keys, vec![1, 2, 3]);
+        assert_eq!(r[&1], "ad");
+        assert_eq!(BTreeMap::<i32, String>::empty(), BTreeMap::new());
+    }
+
+    fn labels(v: &[u8]) -> BTreeSet<u8> {
+        v.iter().copied().collect()
+    }
+
+    #[test]
+    fn btreeset_union() {
+        let r = labels(&[5, 1]).op(labels(&[4, 2]));
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), vec![1, 2, 4, 5]);
+        let r = labels(&[3, 1, 2]).op(labels(&[2, 3, 0]));
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn btreeset_empty() {
+        assert_eq!(BTreeSet::empty().op(labels(&[1])), labels(&[1]));
+        assert_eq!(labels(&[1]).op(BTreeSet::empty()), labels(&[1]));
+        assert!(Monoid::concat(Vec::<BTreeSet<u8>>::new()).is_empty());
+    }
+
+    #[test]
+    fn array_semigroup() {
+        assert_eq!([Sum(1), Sum(2)].op([Sum(3), Sum(4)]), [Sum(4), Sum(6)]);
+        let r = [Max(1), Max(7), Max(3)].op([Max(4), Max(2), Max(3)]);
+        assert_eq!(r, [Max(4), Max(7), Max(3)]);
+        let empty: [Sum<i32>; 0] = [];
+        assert_eq!(empty.op([]), []);
+    }
+
+    #[test]
+    fn array_moves_every_element() {
+        let a = [vec![1], vec![2], vec![3]];
+        let b = [vec![4], vec![5], vec![6]];
+        assert_eq!(a.op(b), [vec![1, 4], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn array_monoid() {
+        let v = vec![[Sum(1), Sum(10)], [Sum(2), Sum(20)]];
+        assert_eq!(Monoid::concat(v), [Sum(3), Sum(30)]);
+        assert_eq!(
+            <[String; 3]>::empty(),
+            [String::new(), String::new(), String::new()]
+        );
+        assert!(<[String; 0]>::empty().is_empty());
+    }
+
+    #[test]
+    fn triple_semigroup() {
+        let a = (Sum(1), Max(3), String::from("a"));
+        let b = (Sum(2), Max(1), String::from("b"));
+        assert_eq!(a.op(b), (Sum(3), Max(3), String::from("ab")));
+    }
+
+    #[test]
+    fn stats_tuple_monoid() {
+        let v = vec![4u32, 8, 1, 5, 2];
+        let r = Monoid::concat(
+            v.into_iter()
+                .map(|x| (Sum(x), Max(x), Sum(1), Product(x), All(x > 0))),
+        );
+        assert_eq!(r, (Sum(20), Max(8), Sum(5), Product(320), All(true)));
+    }
+
+    #[test]
+    fn dodecuple_monoid() {
+        let x = (
+            Sum(1),
+            Product(2),
+            Max(3),
+            Sum(4u64),
+            Any(true),
+            All(false),
+            vec![7],
+            String::from("8"),
+            Sum(9.0),
+            Product(10u8),
+            (),
+            Dual(String::from("12")),
+        );
+        assert_eq!(Semigroup::op(Monoid::empty(), x.clone()), x.clone());
+        assert_eq!(x.clone().op(Monoid::empty()), x);
+        let y = x.clone().op(x).11;
+        assert_eq!(y, Dual(String::from("1212")));
+    }
+
+    #[test]
+    fn box_semigroup() {
+        let r = Box::new(vec![1, 2]).op(Box::new(vec![3]));
+        assert_eq!(*r, vec![1, 2, 3]);
+        let r = Box::new(String::from("a")).op(Box::new(String::from("b")));
+        assert_eq!(*r, "ab");
+    }
+
+    #[test]
+    fn box_reuses_allocation() {
+        let a = Box::new(String::from("a"));
+        let ptr: *const String = &*a;
+        let r = a.op(Box::new(String::from("b")));
+        assert_eq!(ptr, &*r as *const String);
+    }
+
+    #[test]
+    fn box_monoid() {
+        let v = vec![Box::new(Sum(1)), Box::new(Sum(2))];
+        assert_eq!(Monoid::concat(v), Box::new(Sum(3)));
+        assert_eq!(*Box::<String>::empty(), "");
+    }
+
+    #[test]
+    fn cow_str_semigroup() {
+        let r = Cow::Borrowed("a").op(Cow::Borrowed("b"));
+        assert_eq!(r, Cow::<str>::Owned(String::from("ab")));
+        assert!(matches!(r, Cow::Owned(_)));
+        let r = Cow::<str>::Owned(String::from("a")).op(Cow::Borrowed("b"));
+        assert_eq!(r, "ab");
+        let r = Cow::Borrowed("a").op(Cow::Owned(String::from("b")));
+        assert_eq!(r, "ab");
+    }
+
+    #[test]
+    fn cow_borrowed_other_is_not_cloned() {
+        let mut s = String::with_capacity(8);
+        s.push('a');
+        let (r, n) = count_allocations(|| Cow::<str>::Owned(s).op(Cow::Borrowed("b")));
+        assert_eq!((r, n), (Cow::Borrowed("ab"), 0));
+
+        let v = Vec::with_capacity(4);
+        let (r, n) = count_allocations(|| Cow::<[i32]>::Owned(v).op(Cow::Borrowed(&[1, 2])));
+        assert_eq!((r, n), (Cow::Borrowed(&[1, 2][..]), 0));
+    }
+
+    #[test]
+    fn cow_slice_monoid() {
+        let parts: [&[i32]; 3] = [&[1, 2], &[], &[3]];
+        let r: Cow<[i32]> = Monoid::concat(parts.iter().map(|&s| Cow::Borrowed(s)));
+        assert_eq!(&*r, &[1, 2, 3]);
+        assert!(Cow::<[i32]>::empty().is_empty());
+    }
+
+    #[test]
+    fn result_semigroup() {
+        let ok = |x| Ok::<_, &str>(Sum(x));
+        assert_eq!(ok(1).op(ok(2)), ok(3));
+        assert_eq!(ok(1).op(Err("b")), Err("b"));
+        assert_eq!(Err("a").op(ok(2)), Err("a"));
+        assert_eq!(Err::<Sum<i32>, _>("a").op(Err("b")), Err("a"));
+    }
+
+    #[test]
+    fn result_monoid() {
+        let parse = |v: &[&str]| -> Result<Sum<i32>, String> {
+            Monoid::concat(
+                v.iter()
+                    .map(|s| s.parse().map(Sum).map_err(|_| s.to_string())),
+            )
+        };
+        assert_eq!(parse(&["1", "2", "3"]), Ok(Sum(6)));
+        assert_eq!(parse(&["1", "x", "y"]), Err("x".to_string()));
+        assert_eq!(parse(&[]), Ok(Sum(0)));
+    }
+
+    /// Panics when cloned, to check that uniquely owned values are moved.
+    #[derive(Debug, PartialEq)]
+    struct Unique(Vec<i32>);
+
+    impl Clone for Unique {
+        fn clone(&self) -> Self {
+            panic!("unique value was cloned")
+        }
+    }
+
+    impl Semigroup for Unique {
+        fn op(self, other: Self) -> Self {
+            Unique(self.0.op(other.0))
+        }
+    }
+
+    #[test]
+    fn rc_unique_owner() {
+        let r = Rc::new(Unique(vec![1])).op(Rc::new(Unique(vec![2])));
+        assert_eq!(*r, Unique(vec![1, 2]));
+        let r = Arc::new(Unique(vec![1])).op(Arc::new(Unique(vec![2])));
+        assert_eq!(*r, Unique(vec![1, 2]));
+    }
+
+    #[test]
+    fn rc_shared() {
+        let a = Rc::new(vec![1]);
+        let r = a.clone().op(Rc::new(vec![2]));
+        assert_eq!(*r, vec![1, 2]);
+        assert_eq!(*a, vec![1]);
+        assert_eq!(Rc::strong_count(&a), 1);
+        let a = Arc::new(String::from("a"));
+        let r = Monoid::concat(vec![Arc::empty(), a.clone(), a.clone()]);
+        assert_eq!(*r, "aa");
+        assert_eq!(*a, "a");
+    }
+
+    #[test]
+    fn vecdeque_preserves_order() {
+        let deque = |v: &[i32]| v.iter().copied().collect::<VecDeque<_>>();
+        let r = Monoid::concat(vec![
+            deque(&[1]),
+            deque(&[2, 3, 4]),
+            VecDeque::empty(),
+            deque(&[5, 6]),
+        ]);
+        assert_eq!(Vec::from(r), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            Vec::from(deque(&[1, 2, 3]).op(deque(&[4]))),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn linked_list_preserves_order() {
+        let list = |v: &[i32]| v.iter().copied().collect::<LinkedList<_>>();
+        let a = list(&[1, 2]);
+        let r = a.op(list(&[3]));
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        let r = Monoid::concat(vec![
+            list(&[1]),
+            LinkedList::empty(),
+            list(&[2, 3]),
+            list(&[4]),
+        ]);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn binary_heap_merge() {
+        let heap = |v: &[i32]| v.iter().copied().collect::<BinaryHeap<_>>();
+        let r = Monoid::concat(vec![
+            heap(&[3, 9]),
+            BinaryHeap::empty(),
+            heap(&[1, 7, 4]),
+            heap(&[8]),
+        ]);
+        assert_eq!(r.into_sorted_vec(), vec![1, 3, 4, 7, 8, 9]);
+        let mut r = heap(&[2]).op(heap(&[5, 1, 6]));
+        assert_eq!(r.pop(), Some(6));
+        assert_eq!(r.pop(), Some(5));
+        assert_eq!(r.pop(), Some(2));
+        assert_eq!(r.pop(), Some(1));
+        assert_eq!(r.pop(), None);
+    }
+
+    #[test]
+    fn duration_sum() {
+        let latencies = [120, 45, 300].map(Duration::from_millis);
+        assert_eq!(Monoid::concat(latencies), Duration::from_millis(465));
+        assert_eq!(Duration::empty(), Duration::ZERO);
+        assert_eq!(
+            Semigroup::concat(latencies.map(Max)).unwrap().0,
+            Duration::from_millis(300)
+        );
+    }
+
+    #[test]
+    fn duration_saturates() {
+        let r = Duration::MAX.op(Duration::from_secs(1));
+        assert_eq!(r, Duration::MAX);
+    }
+
+    #[test]
+    fn path_join() {
+        let segments = ["usr", "local", "bin"].map(PathBuf::from);
+        assert_eq!(
+            Monoid::concat(segments),
+            PathBuf::from("usr").join("local").join("bin")
+        );
+        let a = PathBuf::from("usr");
+        assert_eq!(a.clone().op(PathBuf::empty()), a);
+        assert_eq!(PathBuf::empty().op(a.clone()), a);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_absolute_replaces() {
+        let r = PathBuf::from("usr/local").op(PathBuf::from("/etc"));
+        assert_eq!(r, PathBuf::from("/etc"));
+    }
+
+    #[test]
+    fn os_string_concat() {
+        let parts = ["PATH", "=", "/usr/bin"].map(OsString::from);
+        assert_eq!(Monoid::concat(parts), OsString::from("PATH=/usr/bin"));
+        assert_eq!(OsString::empty(), OsString::new());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn os_string_non_utf8() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+        let invalid = OsString::from_vec(vec![0x66, 0x6f, 0xff]);
+        let r = OsString::from("a").op(invalid).op(OsString::from("z"));
+        assert_eq!(r.as_bytes(), &[b'a', 0x66, 0x6f, 0xff, b'z']);
+        assert!(r.to_str().is_none());
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Tagged {
+        data: Sum<i32>,
+        _marker: PhantomData<u8>,
+    }
+
+    impl Semigroup for Tagged {
+        fn op(self, other: Self) -> Self {
+            Tagged {
+                data: self.data.op(other.data),
+                _marker: self._marker.op(other._marker),
+            }
+        }
+    }
+
+    impl Monoid for Tagged {
+        fn empty() -> Self {
+            Tagged {
+                data: Monoid::empty(),
+                _marker: Monoid::empty(),
+            }
+        }
+    }
+
+    #[test]
+    fn phantom_data_fields() {
+        let tagged = |x| Tagged {
+            data: Sum(x),
+            _marker: PhantomData,
+        };
+        assert_eq!(
+            Monoid::concat(vec![tagged(1), tagged(2), tagged(3)]),
+            tagged(6)
+        );
+        assert_eq!(Tagged::empty(), Tagged::default());
+    }
+
+    /// Subsets of `{0, 1, 2}` as bit masks, ordered by inclusion.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Subset(u8);
+
+    impl Lattice for Subset {
+        fn join(self, other: Self) -> Self {
+            Subset(self.0 | other.0)
+        }
+
+        fn meet(self, other: Self) -> Self {
+            Subset(self.0 & other.0)
+        }
+    }
+
+    impl BoundedLattice for Subset {
+        fn bottom() -> Self {
+            Subset(0)
+        }
+
+        fn top() -> Self {
+            Subset(0b111)
+        }
+    }
+
+    #[test]
+    fn join_meet_powerset() {
+        let sets = [Subset(0b001), Subset(0b011), Subset(0b101)];
+        assert_eq!(Monoid::concat(sets.map(Join)), Join(Subset(0b111)));
+        assert_eq!(Monoid::concat(sets.map(Meet)), Meet(Subset(0b001)));
+        // {0} and {1} are incomparable, so neither is the join or the meet
+        assert_eq!(Join(Subset(0b001)).op(Join(Subset(0b010))).0, Subset(0b011));
+        assert_eq!(Meet(Subset(0b001)).op(Meet(Subset(0b010))).0, Subset(0b000));
+        assert_eq!(Join::<Subset>::empty().0, Subset(0));
+        assert_eq!(Meet::<Subset>::empty().0, Subset(0b111));
+    }
+
+    #[test]
+    fn join_meet_total_order() {
+        assert_eq!(Monoid::concat([3u8, 9, 4].map(Join)), Join(9));
+        assert_eq!(Monoid::concat([3u8, 9, 4].map(Meet)), Meet(3));
+        assert_eq!(Join::<u8>::empty(), Join(0));
+        assert_eq!(Meet::<u8>::empty(), Meet(255));
+    }
+
+    #[test]
+    fn gcd_monoid() {
+        assert_eq!(Gcd(0).op(Gcd(12)), Gcd(12));
+        assert_eq!(Gcd(12u32).op(Gcd::empty()), Gcd(12));
+        assert_eq!(Gcd(9).op(Gcd(28)), Gcd(1));
+        assert_eq!(Monoid::concat([12u32, 18, 30, 42].map(Gcd)), Gcd(6));
+        assert_eq!(Semigroup::concat([12, 18, -30, 42].map(Gcd)), Some(Gcd(6)));
+        assert_eq!(Gcd(0).op(Gcd(-4)), Gcd(4));
+        assert_eq!(Semigroup::concat(Vec::<Gcd<u8>>::new()), None);
+    }
+
+    #[test]
+    fn lcm_monoid() {
+        assert_eq!(Lcm(1).op(Lcm(7)), Lcm(7));
+        assert_eq!(Lcm(7u32).op(Lcm::empty()), Lcm(7));
+        assert_eq!(Lcm(4).op(Lcm(6)), Lcm(12));
+        assert_eq!(Monoid::concat([2u32, 3, 4, 5].map(Lcm)), Lcm(60));
+        assert_eq!(Semigroup::concat([2, -3, 4, 5].map(Lcm)), Some(Lcm(60)));
+        assert_eq!(Lcm(1).op(Lcm(-4)), Lcm(4));
+        // The product of both operands overflows, but their multiple doesn't
+        let a = 3u64 << 40;
+        let b = 5u64 << 40;
+        assert_eq!(Lcm(a).op(Lcm(b)), Lcm(15 << 40));
+    }
+
+    #[test]
+    fn bitwise_monoids() {
+        let flags = [0b0011u8, 0b0110, 0b1010];
+        assert_eq!(Monoid::concat(flags.map(BitAnd)), BitAnd(0b0010));
+        assert_eq!(Monoid::concat(flags.map(BitOr)), BitOr(0b1111));
+        assert_eq!(Monoid::concat(flags.map(BitXor)), BitXor(0b1111));
+        assert_eq!(BitAnd::<u8>::empty(), BitAnd(0xff));
+        assert_eq!(BitAnd::<u128>::empty(), BitAnd(u128::MAX));
+        assert_eq!(BitOr::<u16>::empty(), BitOr(0));
+        assert_eq!(BitXor::<u64>::empty(), BitXor(0));
+        assert_eq!(
+            BitXor(0b0101u8).op(BitXor(0b0101).inverse()),
+            BitXor::empty()
+        );
+    }
+
+    #[test]
+    fn xor_parity() {
+        assert_eq!(Monoid::concat([true, false, true].map(Xor)), Xor(false));
+        assert_eq!(
+            Monoid::concat([true, true, false, true].map(Xor)),
+            Xor(true)
+        );
+        assert_eq!(Xor::empty(), Xor(false));
+        assert_eq!(Monoid::concat(Vec::<Xor>::new()), Xor(false));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Record {
+        name: &'static str,
+        timestamp: u64,
+    }
+
+    #[test]
+    fn min_by_max_by_key() {
+        let records = || {
+            vec![
+                Record {
+                    name: "a",
+                    timestamp: 20,
+                },
+                Record {
+                    name: "b",
+                    timestamp: 35,
+                },
+                Record {
+                    name: "c",
+                    timestamp: 10,
+                },
+                Record {
+                    name: "d",
+                    timestamp: 35,
+                },
+                Record {
+                    name: "e",
+                    timestamp: 10,
+                },
+            ]
+        };
+        let latest = Semigroup::concat(records().into_iter().map(|r| {
+            let t = r.timestamp;
+            MaxBy::new(r, t)
+        }));
+        let latest = latest.unwrap();
+        assert_eq!(*latest.key(), 35);
+        assert_eq!(latest.into_inner().name, "b");
+        let earliest = Semigroup::concat(records().into_iter().map(|r| {
+            let t = r.timestamp;
+            MinBy::new(r, t)
+        }));
+        assert_eq!(earliest.unwrap().into_inner().name, "c");
+    }
+
+    #[test]
+    fn arg_min_max() {
+        let scores = [(90, 'a'), (95, 'b'), (10, 'c')];
+        let best = Semigroup::concat(scores.map(ArgMax::from)).unwrap();
+        assert_eq!(best.into_inner(), 'b');
+        let worst = Semigroup::concat(scores.map(ArgMin::from)).unwrap();
+        assert_eq!(worst.into_inner(), 'c');
+    }
+
+    #[test]
+    fn arg_min_max_ties() {
+        let scores = [(3, 0), (1, 1), (3, 2), (1, 3)];
+        assert_eq!(
+            Semigroup::concat(scores.map(ArgMax::from)),
+            Some(ArgMax(3, 0))
+        );
+        assert_eq!(
+            Semigroup::concat(scores.map(ArgMin::from)),
+            Some(ArgMin(1, 1))
+        );
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn arg_max_float_keys() {
+        let keys =
+            [(1.0, 'a'), (f64::NAN, 'b'), (2.0, 'c')].map(|(k, v)| ArgMax(OrderedFloat(k), v));
+        let [a, b, c] = keys;
+        assert_eq!(a.op(b).op(c), a.op(b.op(c)));
+        assert_eq!(a.op(b).op(c).into_inner(), 'b');
+    }
+
+    #[test]
+    fn min_max_bounded_identity() {
+        assert_eq!(Monoid::concat([1u32, 2, 3].map(Min)), Min(1));
+        assert_eq!(Monoid::concat([1u32, 2, 3].map(Max)), Max(3));
+        assert_eq!(Min::<i8>::empty(), Min(i8::MAX));
+        assert_eq!(Max::<i8>::empty(), Max(i8::MIN));
+        assert_eq!(Monoid::concat(Vec::<Min<u8>>::new()), Min(u8::MAX));
+    }
+
+    #[test]
+    fn saturating_sum() {
+        assert_eq!(Monoid::concat([200u8, 50, 10].map(SatSum)), SatSum(255));
+        assert_eq!(Monoid::concat([20u8, 50, 10].map(SatSum)), SatSum(80));
+        assert_eq!(SatSum::<u32>::empty(), SatSum(0));
+    }
+
+    #[test]
+    fn wrapping_sum_product() {
+        assert_eq!(Monoid::concat([200u8, 50, 10].map(WrapSum)), WrapSum(4));
+        assert_eq!(Monoid::concat([i8::MAX, 1].map(WrapSum)), WrapSum(i8::MIN));
+        assert_eq!(WrapSum(7u8).op(WrapSum(7).inverse()), WrapSum::empty());
+        assert_eq!(Monoid::concat([16u8, 16, 3].map(WrapProd)), WrapProd(0));
+        assert_eq!(
+            Monoid::concat([3u8, 5, 7, 11].map(WrapProd)),
+            WrapProd((1155 % 256) as u8)
+        );
+        assert_eq!(WrapSum::<u16>::empty(), WrapSum(0));
+        assert_eq!(WrapProd::<u16>::empty(), WrapProd(1));
+    }
+
+    #[test]
+    fn checked_sum() {
+        let total = Monoid::concat([100u32, 250, 75].map(CheckedSum::from));
+        assert_eq!(total, CheckedSum(Some(425)));
+        let overflow = Monoid::concat([u8::MAX, 1, 0, 0].map(CheckedSum::from));
+        assert_eq!(overflow, CheckedSum(None));
+        assert_eq!(
+            CheckedSum(None).op(CheckedSum::from(1i64)),
+            CheckedSum(None)
+        );
+        assert_eq!(CheckedSum::<i64>::empty(), CheckedSum(Some(0)));
+    }
+
+    #[test]
+    fn count_elements() {
+        use crate::foldable::Foldable;
+
+        assert_eq!(vec!['a', 'b', 'c', 'd', 'e'].fold_map(Count::one), Count(5));
+        assert_eq!(Monoid::concat((0..5).map(Count::one)), Count(5));
+        assert_eq!(Vec::<u8>::new().fold_map(Count::one), Count(0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_transparent_round_trip() {
+        fn round_trip<T>(x: T, json: &str)
+        where
+            T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+        {
+            assert_eq!(serde_json::to_string(&x).unwrap(), json);
+            assert_eq!(serde_json::from_str::<T>(json).unwrap(), x);
+        }
+
+        round_trip(Any(true), "true");
+        round_trip(All(false), "false");
+        round_trip(Min(-3), "-3");
+        round_trip(Max(7u8), "7");
+        round_trip(Sum(5), "5");
+        round_trip(Product(2.5), "2.5");
+        round_trip(First::from(String::from("a")), "\"a\"");
+        round_trip(Last(None::<u8>), "null");
+        round_trip(Last::from(vec![1, 2]), "[1,2]");
+    }
+
+    #[test]
+    fn iterator_ext() {
+        let data = [1, 2, 3, 4];
+        assert_eq!(data.iter().copied().map(Sum).mconcat(), Sum(10));
+        assert_eq!(data.iter().copied().map(Max).sconcat(), Some(Max(4)));
+        assert_eq!(std::iter::empty::<Max<i32>>().sconcat(), None);
+        assert_eq!(std::iter::empty::<Sum<i32>>().mconcat(), Sum(0));
+        assert_eq!(data.iter().fold_map(|&x| Product(x)), Product(24));
+        assert_eq!(data.iter().fold_map(|x| x.to_string()), "1234");
+    }
+
+    /// Not a monoid, to check that `First` and `Last` don't need one.
+    #[derive(Debug, PartialEq)]
+    struct Opaque(u8);
+
+    #[test]
+    fn first_last_option_identity() {
+        assert_eq!(
+            Monoid::concat([First(Some(2)), First(Some(5))]),
+            First(Some(2))
+        );
+        assert_eq!(
+            Monoid::concat([Last(Some(2)), Last(Some(5))]),
+            Last(Some(5))
+        );
+        assert_eq!(First::empty().op(First(Some(7))), First(Some(7)));
+        assert_eq!(Last(Some(7)).op(Last::empty()), Last(Some(7)));
+        let v = vec![First(None), First::from(Opaque(1)), First::from(Opaque(2))];
+        assert_eq!(Monoid::concat(v), First(Some(Opaque(1))));
+        let v = vec![Last::from(Opaque(1)), Last::from(Opaque(2)), Last(None)];
+        assert_eq!(Monoid::concat(v), Last(Some(Opaque(2))));
+        assert_eq!(Monoid::concat(Vec::<First<Opaque>>::new()), First(None));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        last: &'static str,
+        first: &'static str,
+        age: u32,
+    }
+
+    #[test]
+    fn comparison_chain() {
+        let person = |last, first, age| Person { last, first, age };
+        let mut people = vec![
+            person("Smith", "John", 40),
+            person("Doe", "Jane", 30),
+            person("Smith", "Anna", 25),
+            person("Smith", "John", 18),
+            person("Doe", "Jane", 22),
+        ];
+        let cmp = Monoid::concat(vec![
+            Comparison::by_key(|p: &Person| p.last),
+            Comparison::by_key(|p: &Person| p.first),
+            Comparison::new(|a: &Person, b: &Person| a.age.cmp(&b.age)),
+        ]);
+        people.sort_by(|a, b| cmp.compare(a, b));
+        assert_eq!(
+            people,
+            vec![
+                person("Doe", "Jane", 22),
+                person("Doe", "Jane", 30),
+                person("Smith", "Anna", 25),
+                person("Smith", "John", 18),
+                person("Smith", "John", 40),
+            ]
+        );
+        let equal = Comparison::<Person>::empty();
+        assert_eq!(equal.compare(&people[0], &people[4]), Ordering::Equal);
+    }
+
+    #[test]
+    fn equivalence_fields() {
+        let person = |last, first, age| Person { last, first, age };
+        let same_name = Equivalence::by_key(|p: &Person| p.last)
+            .op(Equivalence::by_key(|p: &Person| p.first.to_lowercase()));
+        let a = person("Doe", "Jane", 30);
+        assert!(same_name.call(&a, &person("Doe", "jane", 22)));
+        assert!(!same_name.call(&a, &person("Doe", "John", 30)));
+        assert!(!same_name.call(&a, &person("Roe", "Jane", 30)));
+        assert!(Equivalence::<Person>::empty().call(&a, &person("Roe", "John", 1)));
+    }
+
+    #[test]
+    fn reversed_min_selects_max() {
+        use core::cmp::Reverse;
+        let v = [3, 9, 1, 4].iter().map(|&x| Min(Reverse(x)));
+        assert_eq!(Semigroup::concat(v), Some(Min(Reverse(9))));
+    }
+
+    #[test]
+    fn reversed_ordering_chain() {
+        use core::cmp::Reverse;
+        let chain = Reverse(Ordering::Equal).op(Reverse(Ordering::Less));
+        assert_eq!(chain, Reverse(Ordering::Less));
+        assert!(chain > Reverse(Ordering::Equal));
+        assert_eq!(Reverse::<Ordering>::empty(), Reverse(Ordering::Equal));
+
+        let mut people = [
+            Person {
+                last: "Doe",
+                first: "Jane",
+                age: 30,
+            },
+            Person {
+                last: "Smith",
+                first: "Anna",
+                age: 25,
+            },
+            Person {
+                last: "Doe",
+                first: "John",
+                age: 22,
+            },
+        ];
+        let cmp = Comparison::by_key(|p: &Person| p.last)
+            .op(Comparison::new(|a: &Person, b: &Person| a.age.cmp(&b.age)))
+            .reverse();
+        people.sort_by(|a, b| cmp.compare(a, b));
+        let ages: Vec<_> = people.iter().map(|p| p.age).collect();
+        assert_eq!(ages, vec![25, 30, 22]);
+    }
+
+    #[test]
+    fn concat_balanced_matches_fold() {
+        for n in 0..40 {
+            let words: Vec<_> = (0..n).map(|i| i.to_string()).collect();
+            assert_eq!(
+                Semigroup::concat_balanced(words.clone()),
+                Semigroup::concat(words)
+            );
+        }
+        let firsts = (1..=9).map(|x| First(Some(x)));
+        assert_eq!(Semigroup::concat_balanced(firsts), Some(First(Some(1))));
+    }
+
+    #[test]
+    fn concat_balanced_large_input() {
+        let pieces = (0..200_000).map(|_| String::from("abcdefgh"));
+        let joined = Semigroup::concat_balanced(pieces).unwrap();
+        assert_eq!(joined.len(), 1_600_000);
+        assert!(joined.starts_with("abcdefghabcdefgh"));
+    }
+
+    #[test]
+    fn vec_concat_allocates_once() {
+        let parts: Vec<Vec<u32>> = (0..100).map(|i| (0..i).collect()).collect();
+        let expected: Vec<u32> = parts.iter().flatten().copied().collect();
+        let res = Semigroup::concat(parts.clone()).unwrap();
+        assert_eq!(res, expected);
+        assert_eq!(res.capacity(), res.len());
+        assert_eq!(Monoid::concat(parts), expected);
+        assert_eq!(Semigroup::concat(Vec::<Vec<u32>>::new()), None);
+    }
+
+    #[test]
+    fn string_concat_allocates_once() {
+        let parts = (0..1000).map(|i| i.to_string());
+        let res = Semigroup::concat(parts.clone()).unwrap();
+        assert_eq!(res, parts.clone().collect::<String>());
+        assert_eq!(res.capacity(), res.len());
+        assert_eq!(Monoid::concat(parts.take(3)), "012");
+    }
+
+    #[test]
+    fn wrapper_operators() {
+        assert_eq!(Sum(1) + Sum(2), Sum(1).op(Sum(2)));
+        assert_eq!(Product(3) * Product(4), Product(12));
+        assert_eq!(Any(false) | Any(true), Any(true));
+        assert_eq!(All(true) & All(false), All(false));
+
+        let mut total = Sum(String::from("a").len());
+        total += Sum(2);
+        assert_eq!(total, Sum(3));
+        let mut prod = Product(2.5);
+        prod *= Product(2.0);
+        assert_eq!(prod, Product(5.0));
+        let mut any = Any(false);
+        any |= Any(false);
+        assert_eq!(any, Any(false));
+        let mut all = All(true);
+        all &= All(true);
+        assert_eq!(all, All(true));
+    }
+
+    #[test]
+    fn wrapper_helpers() {
+        let total = Monoid::concat((1..=4).map(Sum)).map(|x| x * 10);
+        assert_eq!(total.into_inner(), 100);
+        assert_eq!(Max(3).map(|x| x - 1), Max(2));
+        assert_eq!(*Min(2).as_ref(), 2);
+
+        assert_eq!(First::from(2).map(|x| x + 1).into_inner(), Some(3));
+        assert_eq!(Last::<u32>(None).map(|x| x + 1), Last(None));
+        assert!(!Any(true).map(|b| !b).into_inner());
+        assert!(*All(true).as_ref());
+    }
+
+    #[test]
+    fn wrapper_display() {
+        assert_eq!(format!("{}", Sum(5)), "5");
+        assert_eq!(format!("{}", Product(1.5)), "1.5");
+        assert_eq!(format!("{:>3}", Min(7)), "  7");
+        assert_eq!(format!("{}", Max("top")), "top");
+        assert_eq!(format!("{}", First::from(1)), "1");
+        assert_eq!(format!("{}", Last::<u8>(None)), "");
+        assert_eq!(format!("{} {}", Any(true), All(false)), "true false");
+    }
+
+    #[test]
+    fn mod_sum_wraps_around() {
+        const P: u64 = 1_000_000_007;
+        let big = (0..10).map(|_| ModSum::<P>::new(u64::MAX));
+        let expected = (10 * (u64::MAX as u128) % P as u128) as u64;
+        assert_eq!(Monoid::concat(big), ModSum(expected));
+        assert_eq!(ModSum::<7>(5).op(ModSum(4)), ModSum(2));
+        assert_eq!(ModSum::<7>(3).op(ModSum(3).inverse()), ModSum::empty());
+        assert_eq!(ModSum::<7>(0).inverse(), ModSum(0));
+    }
+
+    #[test]
+    fn mod_product_wraps_around() {
+        const P: u64 = 998_244_353;
+        // Two to the power of 64 by squaring, far beyond `u64`
+        let pow = ModProduct::<P>::new(2).stimes(NonZeroUsize::new(64).unwrap());
+        assert_eq!(pow, ModProduct(((1u128 << 64) % P as u128) as u64));
+        assert_eq!(ModProduct::<P>::empty(), ModProduct(1));
+        assert_eq!(ModProduct::<1>::empty(), ModProduct(0));
+        let factorial = Monoid::concat((1..=20).map(ModProduct::<13>::new));
+        assert_eq!(factorial, ModProduct(0));
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn complex_sum_product() {
+        use num_complex::Complex;
+        let v = [
+            Complex::new(1.0, 2.0),
+            Complex::new(-0.5, 0.25),
+            Complex::new(0.0, -1.0),
+        ];
+        let sum = Monoid::concat(v.iter().copied().map(Sum)).0;
+        assert_eq!(sum, Complex::new(0.5, 1.25));
+        let product = Monoid::concat(v.iter().copied().map(Product)).0;
+        assert_eq!(product, v[0] * v[1] * v[2]);
+        assert_eq!(Product::<Complex<f64>>::empty().0, Complex::new(1.0, 0.0));
+        assert_eq!(Sum(v[0]).inverse().0, -v[0]);
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn bigint_factorial() {
+        use num_bigint::{BigInt, BigUint};
+        let factorial = Monoid::concat((1..=20u32).map(|i| Product(BigInt::from(i)))).0;
+        assert_eq!(factorial, BigInt::from(2_432_902_008_176_640_000u64));
+        let big = Monoid::concat((1..=30u32).map(|i| Product(BigUint::from(i)))).0;
+        assert_eq!(big.to_string(), "265252859812191058636308480000000");
+        assert_eq!(Product::<BigUint>::empty().0, BigUint::from(1u8));
+
+        let sum = Monoid::concat((0..4).map(|_| Sum(BigInt::from(u64::MAX)))).0;
+        assert_eq!(sum, BigInt::from(u64::MAX) * 4);
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn ordered_float_min_max() {
+        let v = [2.5, -1.0, f64::NAN, 7.25];
+        let min = Monoid::concat(v.iter().map(|&x| Min(OrderedFloat(x))));
+        assert_eq!(min, Min(OrderedFloat(-1.0)));
+        // NaN is ordered above every other value
+        let max = Monoid::concat(v.iter().map(|&x| Max(OrderedFloat(x))));
+        assert!(max.0.is_nan());
+        let max = Monoid::concat(v.iter().filter_map(|&x| NotNan::new(x).ok()).map(Max));
+        assert_eq!(max.0.into_inner(), 7.25);
+
+        for x in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN].map(OrderedFloat) {
+            assert_eq!(Min(x).op(Min::empty()), Min(x));
+            assert_eq!(Max(x).op(Max::empty()), Max(x));
+            assert_eq!((Min(x), Sum(1)).op(Monoid::empty()), (Min(x), Sum(1)));
+        }
+        for x in [f32::INFINITY, f32::NEG_INFINITY].map(|x| NotNan::new(x).unwrap()) {
+            assert_eq!(Min(x).op(Min::empty()), Min(x));
+            assert_eq!(Max(x).op(Max::empty()), Max(x));
+        }
+    }
+
+    #[test]
+    fn range_span() {
+        assert_eq!((1..5).op(3..9), 1..9);
+        assert_eq!((3..9).op(1..5), 1..9);
+        assert_eq!((1..3).op(6..9), 1..9);
+        assert_eq!((2..8).op(4..5), 2..8);
+        assert_eq!((core::ops::Range { start: 4, end: 2 }).op(6..9), 6..9);
+        assert_eq!((6..9).op(5..5), 6..9);
+        assert_eq!((5..5).op(core::ops::Range { start: 7, end: 1 }), 5..5);
+        let events = vec![10..12, 3..4, 7..7, 20..25];
+        assert_eq!(Semigroup::concat(events), Some(3..25));
+    }
+
+    #[test]
+    fn wrapper_fmap_changes_type() {
+        assert_eq!(Sum(3i32).fmap(f64::from), Sum(3.0));
+        assert_eq!(
+            Product(4u8).fmap(u32::from).fmap(|x| x << 10),
+            Product(4096u32)
+        );
+        let name = String::from("max");
+        assert_eq!(
+            Max(2u64).fmap(move |x| (x, name)),
+            Max((2, String::from("max")))
+        );
+        assert_eq!(Min(-1i8).fmap(|x| x as i64 * 1000), Min(-1000i64));
+    }
+
+    #[test]
+    fn endo_from_fn_pointers() {
+        let pipeline: [fn(i32) -> i32; 2] = [|x| x + 1, |x| x * 2];
+        let composed = Monoid::concat(pipeline.iter().copied().map(Endo::from));
+        assert_eq!(composed.call(3), 7);
+        let reversed = Monoid::concat(pipeline.iter().rev().copied().map(Endo::from));
+        assert_eq!(reversed.call(3), 8);
+        assert_eq!(Endo::<i32>::empty().call(3), 3);
     }
 }
@@ -0,0 +1,236 @@
+#[cfg(feature = "alloc")]
+use crate::traversable::Effect;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+
+/// A category, given by its morphisms between any two types, an identity morphism for each type,
+/// and an associative composition.
+///
+/// `compose(f, g)` is read as "`f` after `g`", like the `∘` operator: `g` runs first. The
+/// identity and associativity laws read:
+///
+/// - `compose(id(), f) == f == compose(f, id())`
+/// - `compose(f, compose(g, h)) == compose(compose(f, g), h)`
+pub trait Category {
+    type Morphism<A: 'static, B: 'static>;
+
+    fn id<A: 'static>() -> Self::Morphism<A, A>;
+    fn compose<A: 'static, B: 'static, C: 'static>(
+        f: Self::Morphism<B, C>,
+        g: Self::Morphism<A, B>,
+    ) -> Self::Morphism<A, C>;
+}
+
+/// Categories whose morphisms can be built from plain functions, and run alongside other values
+/// in tuples, for wiring computations together without naming their inputs.
+///
+/// `first` and `arr` determine the rest: `second` runs a morphism on the second element instead,
+/// `split` runs two morphisms on either element, and `fanout` runs both on copies of one input.
+pub trait Arrow: Category {
+    fn arr<A: 'static, B: 'static, F: 'static + Fn(A) -> B>(f: F) -> Self::Morphism<A, B>;
+    fn first<A: 'static, B: 'static, C: 'static>(
+        f: Self::Morphism<A, B>,
+    ) -> Self::Morphism<(A, C), (B, C)>;
+
+    fn second<A: 'static, B: 'static, C: 'static>(
+        f: Self::Morphism<A, B>,
+    ) -> Self::Morphism<(C, A), (C, B)> {
+        let swapped = Self::compose(Self::first(f), Self::arr(|(c, a)| (a, c)));
+        Self::compose(Self::arr(|(b, c)| (c, b)), swapped)
+    }
+
+    fn split<A: 'static, B: 'static, C: 'static, D: 'static>(
+        f: Self::Morphism<A, B>,
+        g: Self::Morphism<C, D>,
+    ) -> Self::Morphism<(A, C), (B, D)> {
+        Self::compose(Self::second(g), Self::first(f))
+    }
+
+    fn fanout<A: 'static + Clone, B: 'static, C: 'static>(
+        f: Self::Morphism<A, B>,
+        g: Self::Morphism<A, C>,
+    ) -> Self::Morphism<A, (B, C)> {
+        Self::compose(Self::split(f, g), Self::arr(|a: A| (a.clone(), a)))
+    }
+}
+
+/// A function from `A` to `B`.
+#[cfg(feature = "alloc")]
+pub struct Func<A, B>(Box<dyn Fn(A) -> B>);
+
+#[cfg(feature = "alloc")]
+impl<A, B> Func<A, B> {
+    pub fn new<F: 'static + Fn(A) -> B>(f: F) -> Self {
+        Self(Box::new(f))
+    }
+
+    pub fn run(&self, x: A) -> B {
+        self.0(x)
+    }
+}
+
+/// The category of functions, as `Func`s.
+#[cfg(feature = "alloc")]
+pub struct FuncCategory;
+
+#[cfg(feature = "alloc")]
+impl Category for FuncCategory {
+    type Morphism<A: 'static, B: 'static> = Func<A, B>;
+
+    fn id<A: 'static>() -> Func<A, A> {
+        Func::new(|x| x)
+    }
+
+    fn compose<A: 'static, B: 'static, C: 'static>(f: Func<B, C>, g: Func<A, B>) -> Func<A, C> {
+        Func::new(move |x| f.run(g.run(x)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Arrow for FuncCategory {
+    fn arr<A: 'static, B: 'static, F: 'static + Fn(A) -> B>(f: F) -> Func<A, B> {
+        Func::new(f)
+    }
+
+    fn first<A: 'static, B: 'static, C: 'static>(f: Func<A, B>) -> Func<(A, C), (B, C)> {
+        Func::new(move |(a, c)| (f.run(a), c))
+    }
+}
+
+/// A function from `A` into an effect `MB`, such as `Option<B>` or `Result<B, E>`.
+#[cfg(feature = "alloc")]
+pub struct Kleisli<A, MB>(Box<dyn Fn(A) -> MB>);
+
+#[cfg(feature = "alloc")]
+impl<A, MB> Kleisli<A, MB> {
+    pub fn new<F: 'static + Fn(A) -> MB>(f: F) -> Self {
+        Self(Box::new(f))
+    }
+
+    pub fn run(&self, x: A) -> MB {
+        self.0(x)
+    }
+}
+
+/// The category of Kleisli arrows over the effect of `M`, which can be any instance of the effect,
+/// e.g. `KleisliCategory<Option<()>>` for functions returning options.
+///
+/// Composition chains the effects, and short-circuits once one of them fails.
+#[cfg(feature = "alloc")]
+pub struct KleisliCategory<M>(PhantomData<M>);
+
+#[cfg(feature = "alloc")]
+impl<M: Effect + 'static> Category for KleisliCategory<M> {
+    type Morphism<A: 'static, B: 'static> = Kleisli<A, M::Wrap<B>>;
+
+    fn id<A: 'static>() -> Kleisli<A, M::Wrap<A>> {
+        Kleisli::new(M::from_output)
+    }
+
+    fn compose<A: 'static, B: 'static, C: 'static>(
+        f: Kleisli<B, M::Wrap<C>>,
+        g: Kleisli<A, M::Wrap<B>>,
+    ) -> Kleisli<A, M::Wrap<C>> {
+        Kleisli::new(move |x| match g.run(x).branch() {
+            Ok(y) => f.run(y),
+            Err(r) => M::from_residual(r),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::morphism::{Arrow, Category, Func, FuncCategory, Kleisli, KleisliCategory};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    type Opt = KleisliCategory<Option<()>>;
+
+    fn parse() -> Kleisli<&'static str, Option<i32>> {
+        Kleisli::new(|s: &str| s.parse().ok())
+    }
+
+    fn half() -> Kleisli<i32, Option<i32>> {
+        Kleisli::new(|x| if x % 2 == 0 { Some(x / 2) } else { None })
+    }
+
+    fn describe() -> Kleisli<i32, Option<String>> {
+        Kleisli::new(|x| Some(format!("<{}>", x)))
+    }
+
+    #[test]
+    fn kleisli_compose() {
+        let k = Opt::compose(half(), parse());
+        assert_eq!(k.run("42"), Some(21));
+        assert_eq!(k.run("7"), None);
+        assert_eq!(k.run("nope"), None);
+    }
+
+    #[test]
+    fn kleisli_identity() {
+        for s in ["4", "3", "x"] {
+            assert_eq!(Opt::compose(Opt::id(), parse()).run(s), parse().run(s));
+            assert_eq!(Opt::compose(parse(), Opt::id()).run(s), parse().run(s));
+        }
+    }
+
+    #[test]
+    fn kleisli_associativity() {
+        let left = Opt::compose(describe(), Opt::compose(half(), parse()));
+        let right = Opt::compose(Opt::compose(describe(), half()), parse());
+        for s in ["8", "3", "x"] {
+            assert_eq!(left.run(s), right.run(s));
+        }
+        assert_eq!(left.run("8"), Some("<4>".to_string()));
+    }
+
+    #[test]
+    fn kleisli_short_circuits() {
+        let calls = Rc::new(Cell::new(0));
+        let counted = {
+            let calls = calls.clone();
+            Kleisli::new(move |x: i32| {
+                calls.set(calls.get() + 1);
+                Some(x)
+            })
+        };
+        let k = Opt::compose(counted, parse());
+        assert_eq!(k.run("x"), None);
+        assert_eq!(calls.get(), 0);
+        assert_eq!(k.run("1"), Some(1));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn kleisli_result() {
+        type Res = KleisliCategory<Result<(), String>>;
+        let parse = Kleisli::new(|s: &str| s.parse::<i32>().map_err(|e| e.to_string()));
+        let positive = Kleisli::new(|x: i32| if x > 0 { Ok(x) } else { Err("negative".into()) });
+        let k = Res::compose(positive, parse);
+        assert_eq!(k.run("3"), Ok(3));
+        assert_eq!(k.run("-3"), Err("negative".to_string()));
+    }
+
+    type Fns = FuncCategory;
+
+    #[test]
+    fn func_compose() {
+        let len = Func::new(|s: &str| s.len());
+        let double = Func::new(|x: usize| x * 2);
+        let f = Fns::compose(double, len);
+        assert_eq!(f.run("abc"), 6);
+        assert_eq!(Fns::compose(Fns::id(), f).run("ab"), 4);
+    }
+
+    #[test]
+    fn func_arrow() {
+        let inc = || Fns::arr(|x: i32| x + 1);
+        let show = || Fns::arr(|x: i32| x.to_string());
+        assert_eq!(Fns::first(inc()).run((1, 'a')), (2, 'a'));
+        assert_eq!(Fns::second(inc()).run(('a', 1)), ('a', 2));
+        assert_eq!(Fns::split(inc(), show()).run((1, 5)), (2, "5".to_string()));
+        assert_eq!(Fns::fanout(inc(), show()).run(7), (8, "7".to_string()));
+    }
+}
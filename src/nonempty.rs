@@ -0,0 +1,138 @@
+use crate::traits::*;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// A vector with at least one element, which can always be combined into a single value.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NonEmpty<T> {
+    pub head: T,
+    pub tail: Vec<T>,
+}
+
+impl<T> NonEmpty<T> {
+    pub fn new(head: T, tail: Vec<T>) -> Self {
+        Self { head, tail }
+    }
+
+    pub fn singleton(head: T) -> Self {
+        Self::new(head, Vec::new())
+    }
+
+    pub fn push(&mut self, x: T) {
+        self.tail.push(x);
+    }
+
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+
+    /// Always `false`, for parity with other collections.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn first(&self) -> &T {
+        &self.head
+    }
+
+    pub fn last(&self) -> &T {
+        self.tail.last().unwrap_or(&self.head)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        core::iter::once(&self.head).chain(&self.tail)
+    }
+
+    /// Combines all the elements in order. Unlike `Semigroup::concat`, this can't fail since
+    /// there is always at least one element.
+    pub fn concat(self) -> T
+    where
+        T: Semigroup,
+    {
+        self.tail.into_iter().fold(self.head, T::op)
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = core::iter::Chain<core::iter::Once<T>, alloc::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        core::iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<T> From<T> for NonEmpty<T> {
+    fn from(x: T) -> Self {
+        Self::singleton(x)
+    }
+}
+
+/// Fails on empty vectors, giving them back.
+impl<T> TryFrom<Vec<T>> for NonEmpty<T> {
+    type Error = Vec<T>;
+
+    fn try_from(mut v: Vec<T>) -> Result<Self, Vec<T>> {
+        if v.is_empty() {
+            Err(v)
+        } else {
+            let head = v.remove(0);
+            Ok(Self::new(head, v))
+        }
+    }
+}
+
+impl<T> From<NonEmpty<T>> for Vec<T> {
+    fn from(v: NonEmpty<T>) -> Self {
+        let mut res = Vec::with_capacity(v.len());
+        res.push(v.head);
+        res.extend(v.tail);
+        res
+    }
+}
+
+/// Appends `other` after `self`.
+impl<T> Semigroup for NonEmpty<T> {
+    fn op(mut self, other: Self) -> Self {
+        self.tail.reserve(other.len());
+        self.tail.push(other.head);
+        self.tail.extend(other.tail);
+        self
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::nonempty::NonEmpty;
+    use crate::traits::*;
+    use crate::Sum;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn nonempty_concat() {
+        let v = NonEmpty::new(Sum(1), vec![Sum(2), Sum(3)]);
+        let Sum(total) = v.concat();
+        assert_eq!(total, 6);
+        assert_eq!(NonEmpty::singleton(Sum(4)).concat(), Sum(4));
+        let s = NonEmpty::new(String::from("a"), vec![String::from("b")]);
+        assert_eq!(s.concat(), "ab");
+    }
+
+    #[test]
+    fn nonempty_try_from() {
+        let v = NonEmpty::<i32>::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(*v.first(), 1);
+        assert_eq!(*v.last(), 3);
+        assert_eq!(v.len(), 3);
+        assert_eq!(Vec::from(v.clone()), vec![1, 2, 3]);
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(NonEmpty::<i32>::try_from(vec![]), Err(vec![]));
+    }
+
+    #[test]
+    fn nonempty_semigroup() {
+        let a = NonEmpty::new(1, vec![2]);
+        let b = NonEmpty::from(3);
+        assert_eq!(a.op(b), NonEmpty::new(1, vec![2, 3]));
+    }
+}
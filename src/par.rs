@@ -0,0 +1,56 @@
+//! Parallel reductions over [rayon]'s parallel iterators.
+//!
+//! Parallel reductions combine items in whichever grouping the work is split into, which
+//! associativity makes irrelevant. The order of the items is kept for ordered iterators such as
+//! those over slices and `Vec`s, so any monoid can be combined; iterators without a meaningful
+//! order, such as those over `HashSet`s, call for [`par_concat_unordered`].
+
+use crate::traits::*;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// Combines the items in parallel, giving the same result as [`Monoid::concat`] on an ordered
+/// iterator.
+pub fn par_concat<M: Monoid + Send>(items: impl IntoParallelIterator<Item = M>) -> M {
+    items.into_par_iter().reduce(M::empty, M::op)
+}
+
+/// Combines the items in parallel, which the commutativity of `M` makes independent of their
+/// order.
+pub fn par_concat_unordered<M: CommutativeMonoid + Send>(
+    items: impl IntoParallelIterator<Item = M>,
+) -> M {
+    par_concat(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::par::*;
+    use crate::{Max, Sum};
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+    use std::collections::HashSet;
+
+    #[test]
+    fn par_concat_matches_sequential() {
+        let sums: Vec<_> = (0..10_000u64).map(Sum).collect();
+        assert_eq!(par_concat(sums.clone()), Monoid::concat(sums));
+
+        let maxes: Vec<_> = (0..10_000u64).map(|x| Max(x * 7919 % 10_007)).collect();
+        assert_eq!(par_concat(maxes.clone()), Monoid::concat(maxes));
+    }
+
+    #[test]
+    fn par_concat_keeps_order() {
+        let words: Vec<_> = (0..5_000).map(|i| i.to_string()).collect();
+        assert_eq!(par_concat(words.clone()), Monoid::concat(words));
+        assert_eq!(par_concat(Vec::<String>::new()), "");
+    }
+
+    #[test]
+    fn par_concat_unordered_set() {
+        let set: HashSet<_> = (1..=100u64).collect();
+        assert_eq!(
+            par_concat_unordered(set.into_par_iter().map(Sum)),
+            Sum(5050)
+        );
+    }
+}
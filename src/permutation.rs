@@ -0,0 +1,124 @@
+use crate::traits::*;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// A permutation of the indices `0..n`, sending each index `i` to `self.get(i)`, under
+/// composition. Every index past the end of the permutation is left in place, which makes the
+/// empty permutation the identity for every `n`.
+///
+/// Composition goes right-to-left, like [`Endo`](crate::Endo): `p.op(q)` sends `i` to
+/// `p.get(q.get(i))`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Permutation(Vec<usize>);
+
+impl Permutation {
+    /// Builds a permutation from its images, dropping the trailing indices it leaves in place so
+    /// that equal permutations have the same representation.
+    fn normalized(mut images: Vec<usize>) -> Self {
+        while let Some(&last) = images.last() {
+            if last + 1 != images.len() {
+                break;
+            }
+            images.pop();
+        }
+        Self(images)
+    }
+
+    /// Returns where the permutation sends `i`.
+    pub fn get(&self, i: usize) -> usize {
+        self.0.get(i).copied().unwrap_or(i)
+    }
+
+    /// Moves the item at each index `i` to `self.get(i)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the permutation moves indices past the end of `items`.
+    pub fn apply<T>(&self, items: Vec<T>) -> Vec<T> {
+        assert!(
+            self.0.len() <= items.len(),
+            "permutation of {} indices applied to {} items",
+            self.0.len(),
+            items.len()
+        );
+        let mut res: Vec<Option<T>> = items.iter().map(|_| None).collect();
+        for (i, x) in items.into_iter().enumerate() {
+            res[self.get(i)] = Some(x);
+        }
+        res.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// Checks that the images are a permutation of `0..n`, returning them otherwise.
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = Vec<usize>;
+
+    fn try_from(images: Vec<usize>) -> Result<Self, Vec<usize>> {
+        let mut seen = alloc::vec![false; images.len()];
+        for &j in &images {
+            match seen.get_mut(j) {
+                Some(seen) if !*seen => *seen = true,
+                _ => return Err(images),
+            }
+        }
+        Ok(Self::normalized(images))
+    }
+}
+
+impl Semigroup for Permutation {
+    fn op(self, other: Self) -> Self {
+        let n = self.0.len().max(other.0.len());
+        Self::normalized((0..n).map(|i| self.get(other.get(i))).collect())
+    }
+}
+
+impl DefaultMonoid for Permutation {}
+
+impl Group for Permutation {
+    fn inverse(self) -> Self {
+        let mut res = alloc::vec![0; self.0.len()];
+        for (i, &j) in self.0.iter().enumerate() {
+            res[j] = i;
+        }
+        Self(res)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::permutation::Permutation;
+    use crate::traits::*;
+    use std::convert::TryFrom;
+
+    fn perm(images: Vec<usize>) -> Permutation {
+        Permutation::try_from(images).unwrap()
+    }
+
+    #[test]
+    fn permutation_rejects_invalid() {
+        assert_eq!(Permutation::try_from(vec![0, 2]), Err(vec![0, 2]));
+        assert_eq!(Permutation::try_from(vec![1, 1, 0]), Err(vec![1, 1, 0]));
+        assert_eq!(perm(vec![0, 1, 2]), Permutation::empty());
+    }
+
+    #[test]
+    fn permutation_inverse() {
+        let p = perm(vec![2, 0, 3, 1]);
+        assert_eq!(p.clone().op(p.clone().inverse()), Permutation::empty());
+        assert_eq!(p.clone().inverse().op(p.clone()), Permutation::empty());
+        assert_eq!(p.clone().subtract(p), Permutation::empty());
+    }
+
+    #[test]
+    fn permutation_composition() {
+        let swap = perm(vec![1, 0]);
+        let rotate = perm(vec![1, 2, 0]);
+        let items = vec!['a', 'b', 'c'];
+        assert_eq!(rotate.apply(items.clone()), vec!['c', 'a', 'b']);
+
+        let both = rotate.clone().op(swap.clone());
+        assert_eq!(both.apply(items.clone()), rotate.apply(swap.apply(items)));
+        assert_eq!(both.get(5), 5);
+        assert_eq!(swap.clone().op(swap), Permutation::empty());
+    }
+}
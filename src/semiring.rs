@@ -0,0 +1,542 @@
+use crate::traits::*;
+use num_traits::{Bounded, Zero};
+
+/// A pair of monoids over the same type, where addition is commutative, multiplication
+/// distributes over addition, and `zero` annihilates under multiplication:
+///
+/// - `a.mul(b.add(c)) == a.mul(b).add(a.mul(c))`
+/// - `a.add(b).mul(c) == a.mul(c).add(b.mul(c))`
+/// - `zero().mul(a) == zero() == a.mul(zero())`
+///
+/// Generic algorithms such as matrix multiplication or shortest paths can then run over any
+/// semiring.
+pub trait Semiring: Sized {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+}
+
+macro_rules! impl_semiring {
+    ($zero:literal, $one:literal; $($t:ty)*) => {$(
+        /// The usual addition and multiplication, as in the `Sum` and `Product` monoids.
+        impl Semiring for $t {
+            fn zero() -> Self {
+                $zero
+            }
+
+            fn one() -> Self {
+                $one
+            }
+
+            fn add(self, other: Self) -> Self {
+                self + other
+            }
+
+            fn mul(self, other: Self) -> Self {
+                self * other
+            }
+        }
+    )*};
+}
+
+impl_semiring!(0, 1; u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+impl_semiring!(0.0, 1.0; f32 f64);
+
+/// The boolean semiring, where addition is disjunction and multiplication conjunction, as in the
+/// `Any` and `All` monoids.
+impl Semiring for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn one() -> Self {
+        true
+    }
+
+    fn add(self, other: Self) -> Self {
+        self || other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self && other
+    }
+}
+
+/// The additive monoid of a semiring, combining with `add` from `zero`. For the numeric
+/// primitives, this is the same as `Sum`, which doesn't require a `Semiring`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Additive<S>(pub S);
+
+impl<S: Semiring> Semigroup for Additive<S> {
+    fn op(self, other: Self) -> Self {
+        Additive(self.0.add(other.0))
+    }
+}
+
+impl<S: Semiring> Monoid for Additive<S> {
+    fn empty() -> Self {
+        Additive(S::zero())
+    }
+}
+
+impl<S: Semiring> CommutativeMonoid for Additive<S> {}
+
+/// The multiplicative monoid of a semiring, combining with `mul` from `one`. For the numeric
+/// primitives, this is the same as `Product`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Multiplicative<S>(pub S);
+
+impl<S: Semiring> Semigroup for Multiplicative<S> {
+    fn op(self, other: Self) -> Self {
+        Multiplicative(self.0.mul(other.0))
+    }
+}
+
+impl<S: Semiring> Monoid for Multiplicative<S> {
+    fn empty() -> Self {
+        Multiplicative(S::one())
+    }
+}
+
+/// A semiring with a Kleene star, the infinite sum `one() + x + x*x + x*x*x + ...`, obeying the
+/// fixpoint law `x.star() == one().add(x.mul(x.star()))`.
+///
+/// The star of an adjacency relation gives its reflexive-transitive closure.
+pub trait StarSemiring: Semiring {
+    fn star(self) -> Self;
+}
+
+impl StarSemiring for bool {
+    fn star(self) -> Self {
+        true
+    }
+}
+
+/// The boolean semiring of `bool` as a newtype, alongside the `Any` and `All` monoids which each
+/// expose only one of its operations. Matrices over it compose reachability relations.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BoolRing(pub bool);
+
+impl Semiring for BoolRing {
+    fn zero() -> Self {
+        BoolRing(bool::zero())
+    }
+
+    fn one() -> Self {
+        BoolRing(bool::one())
+    }
+
+    fn add(self, other: Self) -> Self {
+        BoolRing(self.0.add(other.0))
+    }
+
+    fn mul(self, other: Self) -> Self {
+        BoolRing(self.0.mul(other.0))
+    }
+}
+
+impl StarSemiring for BoolRing {
+    fn star(self) -> Self {
+        BoolRing(self.0.star())
+    }
+}
+
+/// A square matrix over a semiring, combining by matrix multiplication. The identity is the
+/// identity matrix, with `one` on the diagonal and `zero` elsewhere.
+///
+/// With `stimes`, this raises matrices to a power: the `k`-th power of an adjacency matrix counts
+/// the paths of length `k`, or finds the shortest ones over [`Tropical`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SquareMatrix<S, const N: usize>(pub [[S; N]; N]);
+
+impl<S: Semiring + Clone, const N: usize> Semigroup for SquareMatrix<S, N> {
+    fn op(self, other: Self) -> Self {
+        let (a, b) = (&self.0, &other.0);
+        SquareMatrix(core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                (0..N).fold(S::zero(), |acc, k| {
+                    acc.add(a[i][k].clone().mul(b[k][j].clone()))
+                })
+            })
+        }))
+    }
+}
+
+impl<S: Semiring + Clone, const N: usize> Monoid for SquareMatrix<S, N> {
+    fn empty() -> Self {
+        SquareMatrix(core::array::from_fn(|i| {
+            core::array::from_fn(|j| if i == j { S::one() } else { S::zero() })
+        }))
+    }
+}
+
+/// Numbers extended with a positive infinity, used as weights of the [`Tropical`] semiring.
+pub trait TropicalWeight: PartialOrd + Zero + Copy {
+    /// The absorbing element of `plus`; `Bounded::max_value` for integers.
+    fn infinity() -> Self;
+    /// The value below every other, absorbing any finite value under `plus`;
+    /// `Bounded::min_value` for signed integers. Unsigned integers are never negative and return
+    /// `0`, which is not absorbing.
+    fn neg_infinity() -> Self;
+    /// Addition where `infinity()` absorbs any other value instead of overflowing.
+    fn plus(self, other: Self) -> Self;
+}
+
+macro_rules! impl_tropical_unsigned {
+    ($($t:ty)*) => {$(
+        impl TropicalWeight for $t {
+            fn infinity() -> Self {
+                <$t as Bounded>::max_value()
+            }
+
+            fn neg_infinity() -> Self {
+                0
+            }
+
+            fn plus(self, other: Self) -> Self {
+                if self == Self::infinity() || other == Self::infinity() {
+                    Self::infinity()
+                } else {
+                    self.saturating_add(other)
+                }
+            }
+        }
+    )*};
+}
+
+impl_tropical_unsigned!(u8 u16 u32 u64 u128 usize);
+
+macro_rules! impl_tropical_signed {
+    ($($t:ty)*) => {$(
+        impl TropicalWeight for $t {
+            fn infinity() -> Self {
+                <$t as Bounded>::max_value()
+            }
+
+            fn neg_infinity() -> Self {
+                <$t as Bounded>::min_value()
+            }
+
+            fn plus(self, other: Self) -> Self {
+                if self == Self::infinity() || other == Self::infinity() {
+                    Self::infinity()
+                } else if self == Self::neg_infinity() || other == Self::neg_infinity() {
+                    Self::neg_infinity()
+                } else {
+                    self.saturating_add(other)
+                }
+            }
+        }
+    )*};
+}
+
+impl_tropical_signed!(i8 i16 i32 i64 i128 isize);
+
+macro_rules! impl_tropical_float {
+    ($($t:ty)*) => {$(
+        impl TropicalWeight for $t {
+            fn infinity() -> Self {
+                <$t>::INFINITY
+            }
+
+            fn neg_infinity() -> Self {
+                <$t>::NEG_INFINITY
+            }
+
+            fn plus(self, other: Self) -> Self {
+                if self == Self::infinity() || other == Self::infinity() {
+                    Self::infinity()
+                } else {
+                    self + other
+                }
+            }
+        }
+    )*};
+}
+
+impl_tropical_float!(f32 f64);
+
+/// The min-plus semiring, where `add` takes the minimum and `mul` adds weights; `zero` is
+/// infinity and `one` is `0`.
+///
+/// Multiplying matrices over this semiring composes paths, keeping the shortest ones, which makes
+/// it the semiring of shortest-path and dynamic-programming problems.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Tropical<T>(pub T);
+
+impl<T: TropicalWeight> Semiring for Tropical<T> {
+    fn zero() -> Self {
+        Tropical(T::infinity())
+    }
+
+    fn one() -> Self {
+        Tropical(T::zero())
+    }
+
+    fn add(self, other: Self) -> Self {
+        if other.0 < self.0 {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Tropical(self.0.plus(other.0))
+    }
+}
+
+/// Going around a non-negative cycle never shortens a path, so its star is `one()`; a negative
+/// cycle can be taken indefinitely, which makes its star negative infinity.
+impl<T: TropicalWeight> StarSemiring for Tropical<T> {
+    fn star(self) -> Self {
+        if self >= Self::one() {
+            Self::one()
+        } else {
+            Tropical(T::neg_infinity())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::semiring::{
+        Additive, BoolRing, Multiplicative, Semiring, SquareMatrix, StarSemiring, Tropical,
+        TropicalWeight,
+    };
+    use crate::traits::*;
+    use crate::{All, Any, Product, Sum};
+    use core::num::NonZeroUsize;
+
+    #[test]
+    fn i64_distributivity() {
+        let v = [-7i64, -1, 0, 1, 2, 13];
+        for &a in &v {
+            for &b in &v {
+                for &c in &v {
+                    assert_eq!(a.mul(b.add(c)), a.mul(b).add(a.mul(c)));
+                    assert_eq!(a.add(b).mul(c), a.mul(c).add(b.mul(c)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn i64_identities() {
+        for a in [-3i64, 0, 5] {
+            assert_eq!(a.add(i64::zero()), a);
+            assert_eq!(a.mul(i64::one()), a);
+            assert_eq!(a.mul(i64::zero()), i64::zero());
+        }
+    }
+
+    #[test]
+    fn primitives_agree_with_monoids() {
+        let v = [-7i64, 0, 1, 13];
+        assert_eq!(i64::zero(), Sum::<i64>::empty().0);
+        assert_eq!(i64::one(), Product::<i64>::empty().0);
+        for &a in &v {
+            for &b in &v {
+                assert_eq!(a.add(b), Sum(a).op(Sum(b)).0);
+                assert_eq!(a.mul(b), Product(a).op(Product(b)).0);
+            }
+        }
+        assert_eq!(f64::one(), Product::<f64>::empty().0);
+        assert_eq!(2.5f64.add(0.5), Sum(2.5).op(Sum(0.5)).0);
+
+        assert_eq!(bool::zero(), Any::empty().0);
+        assert_eq!(bool::one(), All::empty().0);
+        for a in [false, true] {
+            for b in [false, true] {
+                assert_eq!(a.add(b), Any(a).op(Any(b)).0);
+                assert_eq!(a.mul(b), All(a).op(All(b)).0);
+            }
+        }
+    }
+
+    #[test]
+    fn semiring_monoids() {
+        let v = [3i64, -2, 5];
+        assert_eq!(
+            Monoid::concat(v.map(Additive)).0,
+            Monoid::concat(v.map(Sum)).0
+        );
+        assert_eq!(
+            Monoid::concat(v.map(Multiplicative)).0,
+            Monoid::concat(v.map(Product)).0
+        );
+        let paths = [Tropical(7), Tropical(3), Tropical(9)];
+        assert_eq!(Monoid::concat(paths.map(Additive)), Additive(Tropical(3)));
+        assert_eq!(
+            Monoid::concat(paths.map(Multiplicative)),
+            Multiplicative(Tropical(19))
+        );
+        assert_eq!(Additive::<BoolRing>::empty(), Additive(BoolRing(false)));
+    }
+
+    fn square<S: Semiring + Copy>(m: [[S; 3]; 3]) -> [[S; 3]; 3] {
+        let mut res = [[S::zero(); 3]; 3];
+        for (i, row) in res.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                for (a, row) in m[i].iter().zip(&m) {
+                    *cell = cell.add(a.mul(row[j]));
+                }
+            }
+        }
+        res
+    }
+
+    fn graph<T: TropicalWeight>(w: [[Option<T>; 3]; 3]) -> [[Tropical<T>; 3]; 3] {
+        let mut res = [[Tropical::zero(); 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                res[i][j] = if i == j {
+                    Tropical::one()
+                } else {
+                    w[i][j].map_or(Tropical::zero(), Tropical)
+                };
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn tropical_two_hops_float() {
+        let m = graph([
+            [None, Some(1.0), Some(5.0)],
+            [None, None, Some(2.0)],
+            [Some(1.0), None, None],
+        ]);
+        let d = square(m);
+        assert_eq!(d[0][2], Tropical(3.0));
+        assert_eq!(d[1][0], Tropical(3.0));
+        assert_eq!(d[2][1], Tropical(2.0));
+        assert_eq!(d[0][0], Tropical(0.0));
+    }
+
+    #[test]
+    fn tropical_two_hops_int() {
+        let m = graph([
+            [None, Some(1u32), Some(5)],
+            [None, None, Some(2)],
+            [None, None, None],
+        ]);
+        let d = square(m);
+        assert_eq!(d[0][2], Tropical(3));
+        assert_eq!(d[1][0], Tropical::zero());
+        assert_eq!(d[2][0], Tropical(u32::MAX));
+    }
+
+    /// Kleene's algorithm, computing the star of a matrix one intermediate node at a time.
+    fn closure<S: StarSemiring + Copy>(mut m: [[S; 3]; 3]) -> [[S; 3]; 3] {
+        for k in 0..3 {
+            let through = m[k][k].star();
+            let prev = m;
+            for i in 0..3 {
+                for j in 0..3 {
+                    m[i][j] = prev[i][j].add(prev[i][k].mul(through).mul(prev[k][j]));
+                }
+            }
+        }
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = row[i].add(S::one());
+        }
+        m
+    }
+
+    #[test]
+    fn bool_transitive_closure() {
+        let m = [
+            [false, true, false],
+            [false, false, true],
+            [false, false, false],
+        ];
+        let c = closure(m);
+        assert_eq!(
+            c,
+            [
+                [true, true, true],
+                [false, true, true],
+                [false, false, true]
+            ]
+        );
+    }
+
+    #[test]
+    fn star_fixpoint_law() {
+        for x in [false, true] {
+            assert_eq!(x.star(), bool::one().add(x.mul(x.star())));
+        }
+        for x in [-3i32, 0, 4, i32::MAX] {
+            let x = Tropical(x);
+            assert_eq!(x.star(), Tropical::one().add(x.mul(x.star())));
+        }
+        for x in [-1.5, 0.0, 2.0, f64::INFINITY] {
+            let x = Tropical(x);
+            assert_eq!(x.star(), Tropical::one().add(x.mul(x.star())));
+        }
+    }
+
+    #[test]
+    fn tropical_closure() {
+        let inf = Tropical(u32::MAX);
+        let m = [
+            [inf, Tropical(1), Tropical(5)],
+            [inf, inf, Tropical(2)],
+            [Tropical(1), inf, inf],
+        ];
+        let d = closure(m);
+        assert_eq!(d[0][2], Tropical(3));
+        assert_eq!(d[2][1], Tropical(2));
+        assert_eq!(d[1][1], Tropical(0));
+    }
+
+    #[test]
+    fn bool_ring_distributivity() {
+        let v = [BoolRing(false), BoolRing(true)];
+        for &a in &v {
+            assert_eq!(a.add(BoolRing::zero()), a);
+            assert_eq!(a.mul(BoolRing::one()), a);
+            assert_eq!(a.mul(BoolRing::zero()), BoolRing::zero());
+            for &b in &v {
+                for &c in &v {
+                    assert_eq!(a.mul(b.add(c)), a.mul(b).add(a.mul(c)));
+                    assert_eq!(a.add(b).mul(c), a.mul(c).add(b.mul(c)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bool_ring_reachability() {
+        let (f, t) = (BoolRing(false), BoolRing(true));
+        let edges = [[f, t, f], [f, f, t], [t, f, f]];
+        let two_steps = square(edges);
+        assert_eq!(two_steps, [[f, f, t], [t, f, f], [f, t, f]]);
+        assert_eq!(closure(edges), [[t; 3]; 3]);
+    }
+
+    #[test]
+    fn square_matrix_identity() {
+        let m = SquareMatrix([[1u32, 2, 0], [0, 3, 4], [5, 0, 6]]);
+        assert_eq!(m.op(SquareMatrix::empty()), m);
+        assert_eq!(SquareMatrix::empty().op(m), m);
+        assert_eq!(
+            SquareMatrix::<u32, 2>::empty(),
+            SquareMatrix([[1, 0], [0, 1]])
+        );
+    }
+
+    #[test]
+    fn square_matrix_counts_paths() {
+        let edges = SquareMatrix([[0u64, 1, 1], [0, 0, 1], [1, 0, 0]]);
+        let two = edges.op(edges);
+        assert_eq!(two, SquareMatrix([[1, 0, 1], [1, 0, 0], [0, 1, 1]]));
+        let five = edges.stimes(NonZeroUsize::new(5).unwrap());
+        assert_eq!(five, Monoid::concat(core::iter::repeat_n(edges, 5)));
+
+        let reach = SquareMatrix([[false, true], [false, false]]).op(SquareMatrix::empty());
+        assert_eq!(reach, SquareMatrix([[false, true], [false, false]]));
+    }
+}
@@ -0,0 +1,492 @@
+use crate::traits::*;
+#[cfg(feature = "alloc")]
+use alloc::collections::BinaryHeap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cmp::Reverse;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use core::iter::FromIterator;
+use num_traits::Float;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// The arithmetic mean of a stream of values, kept as a running sum and count so that partial
+/// means can be merged in any order.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mean<T> {
+    sum: T,
+    count: u64,
+}
+
+impl<T> Mean<T> {
+    /// The mean of a single value.
+    pub fn new(x: T) -> Self {
+        Self { sum: x, count: 1 }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl<T: Float> Mean<T> {
+    /// Returns the mean of all the values seen, or `None` if there were none.
+    pub fn value(&self) -> Option<T> {
+        if self.count == 0 {
+            None
+        } else {
+            T::from(self.count).map(|n| self.sum / n)
+        }
+    }
+}
+
+impl<T: Float> Semigroup for Mean<T> {
+    fn op(self, other: Self) -> Self {
+        Self {
+            sum: self.sum + other.sum,
+            count: self.count + other.count,
+        }
+    }
+}
+
+impl<T: Float> Monoid for Mean<T> {
+    fn empty() -> Self {
+        Self {
+            sum: T::zero(),
+            count: 0,
+        }
+    }
+}
+
+impl<T: Float> CommutativeMonoid for Mean<T> {}
+
+/// The mean and variance of a stream of values. Partial aggregates are merged with the parallel
+/// algorithm of Chan et al., which stays accurate where a naive sum of squares would lose
+/// precision to cancellation.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Variance {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Variance {
+    /// The variance of a single value.
+    pub fn new(x: f64) -> Self {
+        Self {
+            count: 1,
+            mean: x,
+            m2: 0.0,
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// The population variance, dividing by the number of values.
+    pub fn variance(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.m2 / self.count as f64)
+        }
+    }
+
+    /// The sample variance, dividing by one less than the number of values.
+    pub fn sample_variance(&self) -> Option<f64> {
+        if self.count < 2 {
+            None
+        } else {
+            Some(self.m2 / (self.count - 1) as f64)
+        }
+    }
+}
+
+impl Semigroup for Variance {
+    fn op(self, other: Self) -> Self {
+        let count = self.count + other.count;
+        if count == 0 {
+            return self;
+        }
+        let (na, nb, n) = (self.count as f64, other.count as f64, count as f64);
+        let delta = other.mean - self.mean;
+        Self {
+            count,
+            mean: self.mean + delta * nb / n,
+            m2: self.m2 + other.m2 + delta * delta * na * nb / n,
+        }
+    }
+}
+
+impl DefaultMonoid for Variance {}
+impl CommutativeMonoid for Variance {}
+
+/// The smallest and largest values of a stream, computed in a single pass. The identity is the
+/// empty range, from the greatest value of `T` to its least one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MinMax<T> {
+    pub min: T,
+    pub max: T,
+}
+
+impl<T: Clone> MinMax<T> {
+    pub fn singleton(x: T) -> Self {
+        Self {
+            min: x.clone(),
+            max: x,
+        }
+    }
+}
+
+impl<T: Ord> Semigroup for MinMax<T> {
+    fn op(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+impl<T: Ord + BoundedLattice> Monoid for MinMax<T> {
+    fn empty() -> Self {
+        Self {
+            min: T::top(),
+            max: T::bottom(),
+        }
+    }
+}
+
+impl<T: Ord + BoundedLattice> CommutativeMonoid for MinMax<T> {}
+impl<T: Ord> Band for MinMax<T> {}
+impl<T: Ord + BoundedLattice> Semilattice for MinMax<T> {}
+
+/// The `k` largest values of a stream, such that merging partial results over shards yields the
+/// global top `k`.
+///
+/// The identity can't be given by `Default`, since it doesn't know `k`: `Monoid::empty` keeps no
+/// values, and combining it with a `TopK::new(k)` takes on its `k` as the larger of both. Other
+/// values combined together should all share the same `k`, or the operation isn't associative.
+#[derive(Clone, Debug)]
+#[cfg(feature = "alloc")]
+pub struct TopK<T> {
+    k: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> TopK<T> {
+    /// Keeps the `k` largest values. The heap grows as values are inserted, so a large `k` such as
+    /// `usize::MAX`, keeping every value, doesn't allocate upfront.
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Adds a value, dropping the smallest one if there are now more than `k`.
+    pub fn insert(&mut self, x: T) {
+        self.heap.push(Reverse(x));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the kept values, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(x)| x)
+            .collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Semigroup for TopK<T> {
+    fn op(mut self, mut other: Self) -> Self {
+        self.k = self.k.max(other.k);
+        self.heap.append(&mut other.heap);
+        while self.heap.len() > self.k {
+            self.heap.pop();
+        }
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Monoid for TopK<T> {
+    fn empty() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Counts how many times each key occurs. Merging histograms sums the counts of each key, like
+/// `HashMap<K, Sum<u64>>`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg(feature = "std")]
+pub struct Histogram<K: Eq + Hash>(pub HashMap<K, u64>);
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> Default for Histogram<K> {
+    fn default() -> Self {
+        Histogram(HashMap::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> Histogram<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts one more occurrence of `key`.
+    pub fn record(&mut self, key: K) {
+        *self.0.entry(key).or_insert(0) += 1;
+    }
+
+    pub fn count(&self, key: &K) -> u64 {
+        self.0.get(key).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash + Ord> Histogram<K> {
+    /// Returns the `n` most frequent keys with their counts, most frequent first. Keys with equal
+    /// counts are ordered by key.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, u64)> {
+        let mut counts: Vec<_> = self.0.iter().map(|(k, &c)| (k, c)).collect();
+        counts.sort_by(|(k1, c1), (k2, c2)| c2.cmp(c1).then_with(|| k1.cmp(k2)));
+        counts.truncate(n);
+        counts
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> FromIterator<K> for Histogram<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut histogram = Self::new();
+        for key in iter {
+            histogram.record(key);
+        }
+        histogram
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> Semigroup for Histogram<K> {
+    fn op(self, other: Self) -> Self {
+        let (mut into, from) = if self.0.len() >= other.0.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        for (k, c) in from.0 {
+            *into.0.entry(k).or_insert(0) += c;
+        }
+        into
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> DefaultMonoid for Histogram<K> {}
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> CommutativeMonoid for Histogram<K> {}
+
+/// The probability that independent events all happen, combining by multiplication. The
+/// identity is `1.0`, the probability of no event at all.
+///
+/// See [`ExclusiveProb`] for the probability that any one of mutually exclusive events happens.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Prob(pub f64);
+
+impl Prob {
+    /// Clamps `p` to `[0, 1]`. NaN is kept as is, and propagates through `op`.
+    pub fn new(p: f64) -> Self {
+        Prob(p.clamp(0.0, 1.0))
+    }
+}
+
+impl Default for Prob {
+    fn default() -> Self {
+        Prob(1.0)
+    }
+}
+
+impl Semigroup for Prob {
+    fn op(self, other: Self) -> Self {
+        Prob(self.0 * other.0)
+    }
+}
+
+impl DefaultMonoid for Prob {}
+impl CommutativeMonoid for Prob {}
+
+/// The probability that any one of mutually exclusive events happens, combining by addition. The
+/// identity is `0.0`, the probability of an impossible event.
+///
+/// Sums above `1` can only come from events which aren't exclusive after all, and are clamped
+/// to `1`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct ExclusiveProb(pub f64);
+
+impl ExclusiveProb {
+    /// Clamps `p` to `[0, 1]`. NaN is kept as is, and propagates through `op`.
+    pub fn new(p: f64) -> Self {
+        ExclusiveProb(p.clamp(0.0, 1.0))
+    }
+}
+
+impl Semigroup for ExclusiveProb {
+    fn op(self, other: Self) -> Self {
+        ExclusiveProb((self.0 + other.0).min(1.0))
+    }
+}
+
+impl DefaultMonoid for ExclusiveProb {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::stats::{ExclusiveProb, Histogram, Mean, MinMax, Prob, TopK, Variance};
+    use crate::traits::*;
+
+    #[test]
+    fn mean_merges_partial_means() {
+        let data = [1.0, 2.0, 4.0, 8.0, 10.0, 5.0];
+        let left = Monoid::concat(data[..2].iter().copied().map(Mean::new));
+        let right = Monoid::concat(data[2..].iter().copied().map(Mean::new));
+        let whole = Monoid::concat(data.iter().copied().map(Mean::new));
+        assert_eq!(left.op(right), whole);
+        assert_eq!(whole.value(), Some(5.0));
+        assert_eq!(whole.count(), 6);
+        assert_eq!(Mean::<f64>::empty().value(), None);
+    }
+
+    fn two_pass(data: &[f64]) -> (f64, f64) {
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        (mean, variance)
+    }
+
+    #[test]
+    fn variance_merges_shards() {
+        let data: Vec<f64> = (0..100).map(|i| 1e9 + (i * 7 % 13) as f64).collect();
+        let (mean, variance) = two_pass(&data);
+        let shards = data
+            .chunks(17)
+            .map(|c| Monoid::concat(c.iter().copied().map(Variance::new)));
+        let merged = Monoid::concat(shards);
+        assert_eq!(merged.count(), 100);
+        assert!((merged.mean().unwrap() - mean).abs() < 1e-6);
+        assert!((merged.variance().unwrap() - variance).abs() < 1e-6);
+        let sample = merged.sample_variance().unwrap();
+        assert!((sample - variance * 100.0 / 99.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn variance_empty() {
+        let empty = Variance::empty();
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.variance(), None);
+        assert_eq!(Variance::new(3.0).op(empty), Variance::new(3.0));
+        assert_eq!(Variance::new(3.0).variance(), Some(0.0));
+        assert_eq!(Variance::new(3.0).sample_variance(), None);
+    }
+
+    #[test]
+    fn min_max_range() {
+        let r = Monoid::concat([3, 1, 4, 1, 5].map(MinMax::singleton));
+        assert_eq!(r, MinMax { min: 1, max: 5 });
+        assert_eq!(r.op(MinMax::empty()), r);
+        assert_eq!(MinMax::empty().op(r), r);
+        assert_eq!(MinMax::<u8>::empty(), MinMax { min: 255, max: 0 });
+    }
+
+    fn top3(shard: &[u32]) -> TopK<u32> {
+        let mut top = TopK::new(3);
+        for &x in shard {
+            top.insert(x);
+        }
+        top
+    }
+
+    #[test]
+    fn top_k_merges_shards() {
+        let shards = [
+            top3(&[5, 1, 9, 12]),
+            top3(&[3, 30]),
+            top3(&[7, 11, 2, 8, 10]),
+        ];
+        let top = Monoid::concat(shards);
+        assert_eq!(top.k(), 3);
+        assert_eq!(top.into_sorted_vec(), vec![30, 12, 11]);
+        assert_eq!(
+            top3(&[4, 2]).op(TopK::empty()).into_sorted_vec(),
+            vec![4, 2]
+        );
+        assert_eq!(TopK::empty().op(top3(&[4, 2])).k(), 3);
+    }
+
+    #[test]
+    fn top_k_keeps_everything() {
+        let mut all = TopK::new(usize::MAX);
+        for x in [3, 1, 2] {
+            all.insert(x);
+        }
+        let top = all.op(top3(&[5, 4, 6, 0]));
+        assert_eq!(top.k(), usize::MAX);
+        assert_eq!(top.into_sorted_vec(), vec![6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn histogram_merge() {
+        let a: Histogram<_> = "abracadabra".chars().collect();
+        let b: Histogram<_> = "cabbage".chars().collect();
+        let h = a.op(b).op(Histogram::empty());
+        assert_eq!(h.count(&'a'), 7);
+        assert_eq!(h.count(&'b'), 4);
+        assert_eq!(h.count(&'z'), 0);
+        assert_eq!(h.most_common(3), vec![(&'a', 7), (&'b', 4), (&'c', 2)]);
+        // 'c' and 'r' both occur twice, and are ordered by key
+        assert_eq!(h.most_common(4)[3], (&'r', 2));
+    }
+
+    #[test]
+    fn prob_independent_events() {
+        let pipeline = [0.9, 0.5, 0.8].iter().copied().map(Prob::new);
+        let p = Monoid::concat(pipeline).0;
+        assert!((p - 0.36).abs() < 1e-12);
+        assert_eq!(Prob::empty(), Prob(1.0));
+        assert_eq!(Prob::new(0.25).op(Prob::empty()), Prob(0.25));
+        assert_eq!(Prob::new(1.5), Prob(1.0));
+        assert_eq!(Prob::new(-0.5), Prob(0.0));
+    }
+
+    #[test]
+    fn exclusive_prob_sums() {
+        let faces = (0..4).map(|_| ExclusiveProb::new(0.125));
+        assert_eq!(Monoid::concat(faces), ExclusiveProb(0.5));
+        assert_eq!(ExclusiveProb::empty(), ExclusiveProb(0.0));
+        assert_eq!(
+            ExclusiveProb(0.75).op(ExclusiveProb(0.5)),
+            ExclusiveProb(1.0)
+        );
+    }
+}
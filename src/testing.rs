@@ -0,0 +1,197 @@
+//! Assertions checking that a type obeys the laws of the algebraic traits, for testing custom
+//! implementations. They panic with the values involved when a law doesn't hold.
+//!
+//! Fixed samples only go so far; a property-testing library such as [proptest] can feed them
+//! random values instead:
+//!
+//! ```
+//! use category::testing::assert_associative;
+//! use category::Max;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn max_associative(a: i64, b: i64, c: i64) {
+//!         assert_associative(Max(a), Max(b), Max(c));
+//!     }
+//! }
+//! # max_associative();
+//! ```
+//!
+//! [proptest]: https://docs.rs/proptest
+
+use crate::traits::*;
+use core::fmt::Debug;
+
+/// Asserts that `a.op(b).op(c) == a.op(b.op(c))`.
+pub fn assert_associative<M: Semigroup + PartialEq + Clone + Debug>(a: M, b: M, c: M) {
+    let left = a.clone().op(b.clone()).op(c.clone());
+    let right = a.clone().op(b.clone().op(c.clone()));
+    assert_eq!(
+        left, right,
+        "`op` isn't associative for {:?}, {:?} and {:?}",
+        a, b, c
+    );
+}
+
+/// Asserts that `M::empty().op(x) == x`.
+pub fn assert_left_identity<M: Monoid + PartialEq + Clone + Debug>(x: M) {
+    assert_eq!(
+        M::empty().op(x.clone()),
+        x,
+        "`empty` isn't a left identity for {:?}",
+        x
+    );
+}
+
+/// Asserts that `x.op(M::empty()) == x`.
+pub fn assert_right_identity<M: Monoid + PartialEq + Clone + Debug>(x: M) {
+    assert_eq!(
+        x.clone().op(M::empty()),
+        x,
+        "`empty` isn't a right identity for {:?}",
+        x
+    );
+}
+
+/// Generates a module of tests checking the monoid laws for a type, given either fixed samples
+/// of it or a [proptest] strategy generating them. Associativity is checked on every triple of
+/// samples, and the identity laws on every sample.
+///
+/// ```
+/// # use category::monoid_laws;
+/// use category::Sum;
+/// use proptest::prelude::*;
+///
+/// monoid_laws!(sum_fixed: Sum<i32> { samples: [Sum(-3), Sum(0), Sum(12)] });
+/// monoid_laws!(sum_random: Sum<i32> { strategy: (-1000..1000).prop_map(Sum) });
+/// # fn main() {}
+/// ```
+///
+/// The samples form needs neither `std` nor an allocator. The strategy form needs `proptest` as a
+/// dependency of the calling crate. The generated module
+/// imports everything from its parent, which the samples and strategies can refer to.
+///
+/// [proptest]: https://docs.rs/proptest
+#[macro_export]
+macro_rules! monoid_laws {
+    ($name:ident: $ty:ty { samples: [$($sample:expr),* $(,)?] $(,)? }) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn samples() -> impl Iterator<Item = $ty> + Clone {
+                ::core::iter::IntoIterator::into_iter([$($sample),*])
+            }
+
+            #[test]
+            fn associative() {
+                for a in samples() {
+                    for b in samples() {
+                        for c in samples() {
+                            $crate::testing::assert_associative(a.clone(), b.clone(), c);
+                        }
+                    }
+                }
+            }
+
+            #[test]
+            fn left_identity() {
+                samples().for_each($crate::testing::assert_left_identity);
+            }
+
+            #[test]
+            fn right_identity() {
+                samples().for_each($crate::testing::assert_right_identity);
+            }
+        }
+    };
+    ($name:ident: $ty:ty { strategy: $strategy:expr $(,)? }) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            ::proptest::proptest! {
+                #[test]
+                fn associative(a in $strategy, b in $strategy, c in $strategy) {
+                    $crate::testing::assert_associative::<$ty>(a, b, c);
+                }
+
+                #[test]
+                fn left_identity(x in $strategy) {
+                    $crate::testing::assert_left_identity::<$ty>(x);
+                }
+
+                #[test]
+                fn right_identity(x in $strategy) {
+                    $crate::testing::assert_right_identity::<$ty>(x);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::testing::*;
+    use crate::{Max, Sum};
+    use proptest::prelude::*;
+
+    #[test]
+    fn laws_hold() {
+        for &(a, b, c) in &[(1, 2, 3), (-4, 0, 9), (i32::MIN, 5, i32::MAX)] {
+            assert_associative(Max(a), Max(b), Max(c));
+            assert_left_identity(Max(a));
+            assert_right_identity(Max(c));
+        }
+        assert_associative(Sum(1u64), Sum(20), Sum(300));
+        assert_right_identity(Sum(7u8));
+        let s = |x: &str| String::from(x);
+        assert_associative(s("ab"), s(""), s("cd"));
+        assert_left_identity(s("ef"));
+    }
+
+    /// Subtraction, which isn't associative, and whose "identity" `0` is only a right identity.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Diff(i32);
+
+    impl Semigroup for Diff {
+        fn op(self, other: Self) -> Self {
+            Diff(self.0 - other.0)
+        }
+    }
+
+    impl Monoid for Diff {
+        fn empty() -> Self {
+            Diff(0)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't associative")]
+    fn broken_associativity() {
+        assert_associative(Diff(1), Diff(2), Diff(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't a left identity")]
+    fn broken_identity() {
+        assert_right_identity(Diff(5));
+        assert_left_identity(Diff(5));
+    }
+
+    proptest! {
+        #[test]
+        fn string_laws(a: String, b: String, c: String) {
+            assert_associative(a.clone(), b, c);
+            assert_left_identity(a.clone());
+            assert_right_identity(a);
+        }
+    }
+
+    crate::monoid_laws!(sum_i32: Sum<i32> { samples: [Sum(-7), Sum(0), Sum(1), Sum(42)] });
+    crate::monoid_laws!(string: String {
+        samples: [String::new(), String::from("a"), String::from("bc")],
+    });
+    crate::monoid_laws!(sum_i32_random: Sum<i32> { strategy: (-1000..1000).prop_map(Sum) });
+    crate::monoid_laws!(string_random: String { strategy: ".{0,8}" });
+}
@@ -1,4 +1,34 @@
-use std::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Cow, ToOwned};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use core::marker::PhantomData;
+use core::num::NonZeroUsize;
+use core::ops::Range;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::ffi::OsString;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+/// Derives `Semigroup` for a struct, combining each field with its own `op`.
+#[cfg(feature = "derive")]
+pub use category_derive::Semigroup;
 
 pub trait Semigroup: Sized {
     fn op(self, other: Self) -> Self;
@@ -11,15 +41,65 @@ pub trait Semigroup: Sized {
         })
     }
 
+    /// Combines the items like [`Semigroup::concat`], but in a balanced tree of applications of
+    /// `op` rather than a left fold. Each item then takes part in `O(log n)` applications instead of
+    /// up to `n`, which matters when `op` costs as much as its operands, such as copying a `String`.
+    fn concat_balanced<I: Iterator<Item = Self>>(
+        this: impl IntoIterator<Item = Self, IntoIter = I>,
+    ) -> Option<Self> {
+        // A binary counter: slot `i` holds the combination of `2^i` consecutive items, the slots
+        // further up holding the earlier ones.
+        let mut slots: [Option<Self>; usize::BITS as usize] = core::array::from_fn(|_| None);
+        for x in this {
+            let mut carry = x;
+            let mut i = 0;
+            while let Some(y) = slots[i].take() {
+                carry = y.op(carry);
+                i += 1;
+            }
+            slots[i] = Some(carry);
+        }
+        IntoIterator::into_iter(slots)
+            .flatten()
+            .reduce(|acc, x| x.op(acc))
+    }
+
+    /// Combines `self` with `n` additional copies of itself, so that the result holds `n + 1`
+    /// terms in total: `x.repeat(0) == x`, `x.repeat(2) == x.op(x).op(x)`.
     fn repeat(self, n: usize) -> Self
     where
-        Self: Copy,
+        Self: Clone,
     {
-        let mut res = self;
+        let mut res = self.clone();
         for _ in 0..n {
-            res = res.op(res);
+            res = res.op(self.clone());
+        }
+        res
+    }
+
+    /// Combines `n` copies of `self` in `O(log n)` applications of `op`, by exponentiation by
+    /// squaring. Only powers of `self` are ever combined together, so the result is the same as
+    /// the left-to-right fold even for non-commutative semigroups.
+    fn stimes(self, n: NonZeroUsize) -> Self
+    where
+        Self: Clone,
+    {
+        let mut n = n.get();
+        let mut base = self;
+        while n & 1 == 0 {
+            base = base.clone().op(base);
+            n >>= 1;
+        }
+        let mut res = base.clone();
+        n >>= 1;
+        while n > 0 {
+            base = base.clone().op(base);
+            if n & 1 == 1 {
+                res = res.op(base.clone());
+            }
+            n >>= 1;
         }
-        return res;
+        res
     }
 }
 
@@ -33,10 +113,16 @@ impl Semigroup for Ordering {
     }
 }
 
-impl Semigroup for () {
-    fn op(self, _other: Self) -> Self {
-        ()
+/// Forwards to the wrapped semigroup. For `Reverse<Ordering>`, this chains comparisons made
+/// under the reversed order the same way `Ordering` chains them under the original one.
+impl<T: Semigroup> Semigroup for Reverse<T> {
+    fn op(self, other: Self) -> Self {
+        Reverse(self.0.op(other.0))
     }
+}
+
+impl Semigroup for () {
+    fn op(self, _other: Self) -> Self {}
 
     fn concat<I: Iterator<Item = Self>>(
         _: impl IntoIterator<Item = Self, IntoIter = I>,
@@ -46,23 +132,201 @@ impl Semigroup for () {
 
     fn repeat(self, _: usize) -> Self
     where
-        Self: Copy,
+        Self: Clone,
     {
-        ()
     }
 }
 
+impl<T: ?Sized> Semigroup for PhantomData<T> {
+    fn op(self, _other: Self) -> Self {
+        PhantomData
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<T> Semigroup for Vec<T> {
-    fn op(self, other: Self) -> Self {
-        self.into_iter().chain(other.into_iter()).collect()
+    fn op(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+
+    /// Allocates the result once, at its total length.
+    fn concat<I: Iterator<Item = Self>>(
+        this: impl IntoIterator<Item = Self, IntoIter = I>,
+    ) -> Option<Self> {
+        let parts: Vec<Self> = this.into_iter().collect();
+        if parts.is_empty() {
+            return None;
+        }
+        let mut res = Vec::with_capacity(parts.iter().map(Vec::len).sum());
+        for mut part in parts {
+            res.append(&mut part);
+        }
+        Some(res)
     }
 }
 
+/// Appends `other` after `self`, extending whichever deque is longer in place.
+#[cfg(feature = "alloc")]
+impl<T> Semigroup for VecDeque<T> {
+    fn op(mut self, mut other: Self) -> Self {
+        if self.len() >= other.len() {
+            self.extend(other);
+            self
+        } else {
+            for x in self.into_iter().rev() {
+                other.push_front(x);
+            }
+            other
+        }
+    }
+}
+
+/// Splices `other` after `self` in constant time.
+#[cfg(feature = "alloc")]
+impl<T> Semigroup for LinkedList<T> {
+    fn op(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl Semigroup for String {
     fn op(mut self, other: Self) -> Self {
         self.push_str(&other);
         self
     }
+
+    /// Allocates the result once, at its total length.
+    fn concat<I: Iterator<Item = Self>>(
+        this: impl IntoIterator<Item = Self, IntoIter = I>,
+    ) -> Option<Self> {
+        let parts: Vec<Self> = this.into_iter().collect();
+        if parts.is_empty() {
+            return None;
+        }
+        let mut res = String::with_capacity(parts.iter().map(String::len).sum());
+        for part in parts {
+            res.push_str(&part);
+        }
+        Some(res)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Semigroup for OsString {
+    fn op(mut self, other: Self) -> Self {
+        self.push(other);
+        self
+    }
+}
+
+/// Joins both paths like [`Path::join`](std::path::Path::join), so an absolute `other` replaces
+/// `self` entirely. An empty `other` is skipped, rather than adding a trailing separator, so that
+/// the empty path is an identity.
+#[cfg(feature = "std")]
+impl Semigroup for PathBuf {
+    fn op(mut self, other: Self) -> Self {
+        if !other.as_os_str().is_empty() {
+            self.push(other);
+        }
+        self
+    }
+}
+
+/// Adds both durations, saturating at [`Duration::MAX`] instead of overflowing.
+impl Semigroup for Duration {
+    fn op(self, other: Self) -> Self {
+        self.saturating_add(other)
+    }
+}
+
+/// Returns the smallest half-open range covering both ranges, including any gap between them:
+/// `(1..3).op(6..9) == 1..9`. Empty ranges cover nothing and are skipped, or kept as the first
+/// one when both are empty.
+///
+/// There is no identity, as any empty range would do but they don't compare equal; folds can
+/// start from `None` with `Option<Range<T>>` instead.
+impl<T: Ord> Semigroup for Range<T> {
+    fn op(self, other: Self) -> Self {
+        if other.is_empty() {
+            self
+        } else if self.is_empty() {
+            other
+        } else {
+            self.start.min(other.start)..self.end.max(other.end)
+        }
+    }
+}
+
+impl<T: Ord> Band for Range<T> {}
+
+/// Combines two `Ok` values, or returns the first `Err`.
+impl<T: Semigroup, E> Semigroup for Result<T, E> {
+    fn op(self, other: Self) -> Self {
+        match (self, other) {
+            (Ok(a), Ok(b)) => Ok(a.op(b)),
+            (Err(e), _) | (_, Err(e)) => Err(e),
+        }
+    }
+}
+
+/// Combines the shared values, moving them out of their pointers when those are the only owners.
+/// Values which are still shared elsewhere are cloned instead.
+#[cfg(feature = "alloc")]
+impl<T: Semigroup + Clone> Semigroup for Rc<T> {
+    fn op(self, other: Self) -> Self {
+        let a = Rc::try_unwrap(self).unwrap_or_else(|rc| T::clone(&rc));
+        let b = Rc::try_unwrap(other).unwrap_or_else(|rc| T::clone(&rc));
+        Rc::new(a.op(b))
+    }
+}
+
+/// Combines the shared values like the `Rc` instance.
+#[cfg(feature = "alloc")]
+impl<T: Semigroup + Clone> Semigroup for Arc<T> {
+    fn op(self, other: Self) -> Self {
+        let a = Arc::try_unwrap(self).unwrap_or_else(|arc| T::clone(&arc));
+        let b = Arc::try_unwrap(other).unwrap_or_else(|arc| T::clone(&arc));
+        Arc::new(a.op(b))
+    }
+}
+
+/// Combines the boxed values, reusing `self`'s allocation for the result.
+#[cfg(feature = "alloc")]
+impl<T: Semigroup> Semigroup for Box<T> {
+    fn op(mut self, other: Self) -> Self {
+        let x = *self;
+        *self = x.op(*other);
+        self
+    }
+}
+
+/// Appends `other` to the owned form of `self`, which only allocates to take ownership of a
+/// borrowed `self`: a borrowed `other` is copied straight into it. The result is always
+/// `Cow::Owned`.
+#[cfg(feature = "alloc")]
+impl<'a> Semigroup for Cow<'a, str> {
+    fn op(self, other: Self) -> Self {
+        let mut s = self.into_owned();
+        s.push_str(&other);
+        Cow::Owned(s)
+    }
+}
+
+/// Appends `other` to the owned form of `self`, like `Cow<str>`. The elements of an owned
+/// `other` are moved rather than cloned.
+#[cfg(feature = "alloc")]
+impl<'a, T: Clone> Semigroup for Cow<'a, [T]> {
+    fn op(self, other: Self) -> Self {
+        let mut v = self.into_owned();
+        match other {
+            Cow::Borrowed(other) => v.extend_from_slice(other),
+            Cow::Owned(mut other) => v.append(&mut other),
+        }
+        Cow::Owned(v)
+    }
 }
 
 impl<T: Semigroup> Semigroup for Option<T> {
@@ -75,17 +339,120 @@ impl<T: Semigroup> Semigroup for Option<T> {
     }
 }
 
-impl<T: Semigroup, U: Semigroup> Semigroup for (T, U) {
+/// Takes the union of both maps, combining the values of keys present in both. The smaller map is
+/// moved into the larger one, but values are always combined as `self`'s value first.
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V: Semigroup, S: BuildHasher> Semigroup for HashMap<K, V, S> {
+    fn op(self, other: Self) -> Self {
+        let (mut into, from, from_left) = if self.len() >= other.len() {
+            (self, other, false)
+        } else {
+            (other, self, true)
+        };
+        for (k, v) in from {
+            let v = match into.remove(&k) {
+                Some(w) if from_left => v.op(w),
+                Some(w) => w.op(v),
+                None => v,
+            };
+            into.insert(k, v);
+        }
+        into
+    }
+}
+
+/// Takes the union of both maps like the `HashMap` instance, keeping keys sorted.
+#[cfg(feature = "alloc")]
+impl<K: Ord, V: Semigroup> Semigroup for BTreeMap<K, V> {
+    fn op(self, other: Self) -> Self {
+        let (mut into, from, from_left) = if self.len() >= other.len() {
+            (self, other, false)
+        } else {
+            (other, self, true)
+        };
+        for (k, v) in from {
+            let v = match into.remove(&k) {
+                Some(w) if from_left => v.op(w),
+                Some(w) => w.op(v),
+                None => v,
+            };
+            into.insert(k, v);
+        }
+        into
+    }
+}
+
+/// Takes the union of both sets, extending the larger one.
+#[cfg(feature = "alloc")]
+impl<T: Ord> Semigroup for BTreeSet<T> {
+    fn op(self, other: Self) -> Self {
+        let (mut into, from) = if self.len() >= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        into.extend(from);
+        into
+    }
+}
+
+/// Takes the union of both sets, extending the larger one.
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher> Semigroup for HashSet<T, S> {
     fn op(self, other: Self) -> Self {
-        (self.0.op(other.0), self.1.op(other.1))
+        let (mut into, from) = if self.len() >= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        into.extend(from);
+        into
+    }
+}
+
+/// Merges both heaps with [`BinaryHeap::append`], which moves the smaller heap into the larger
+/// one. This costs `O(m log(n + m))` when inserting the `m` elements of the smaller heap one by
+/// one, or `O(n + m)` when rebuilding the whole heap is cheaper.
+#[cfg(feature = "alloc")]
+impl<T: Ord> Semigroup for BinaryHeap<T> {
+    fn op(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
     }
 }
 
+/// Combines both arrays element-wise.
+impl<T: Semigroup, const N: usize> Semigroup for [T; N] {
+    fn op(self, other: Self) -> Self {
+        let mut other = IntoIterator::into_iter(other);
+        self.map(|x| match other.next() {
+            Some(y) => x.op(y),
+            None => unreachable!("arrays have the same length"),
+        })
+    }
+}
+
+/// Derives `Monoid` for a struct, building `empty` from the `empty` of each field.
+#[cfg(feature = "derive")]
+pub use category_derive::Monoid;
+
 pub trait Monoid: Semigroup {
     fn empty() -> Self;
     fn concat(this: impl IntoIterator<Item = Self>) -> Self {
         this.into_iter().fold(Self::empty(), Semigroup::op)
     }
+
+    /// Combines `n` copies of `self`, returning `empty()` when `n == 0`. See
+    /// [`Semigroup::stimes`].
+    fn mtimes(self, n: usize) -> Self
+    where
+        Self: Clone,
+    {
+        match NonZeroUsize::new(n) {
+            Some(n) => self.stimes(n),
+            None => Self::empty(),
+        }
+    }
 }
 
 pub trait DefaultMonoid: Default + Semigroup {}
@@ -94,28 +461,391 @@ impl<T: DefaultMonoid> Monoid for T {
     fn empty() -> Self {
         T::default()
     }
+
+    // Goes through `Semigroup::concat` to pick up the specialized versions, such as those of
+    // `Vec` and `String`
+    fn concat(this: impl IntoIterator<Item = Self>) -> Self {
+        Semigroup::concat(this).unwrap_or_default()
+    }
 }
 
+impl DefaultMonoid for () {}
+
+impl<T: ?Sized> DefaultMonoid for PhantomData<T> {}
+
 impl Monoid for Ordering {
     fn empty() -> Self {
         Self::Equal
     }
 }
 
-impl<T> Monoid for Vec<T> {
+impl<T: Monoid> Monoid for Reverse<T> {
     fn empty() -> Self {
-        Vec::with_capacity(0) // Preventing allocation
+        Reverse(T::empty())
     }
 }
 
-impl Monoid for String {
+// Neither `Vec::default` nor `String::default` allocate
+#[cfg(feature = "alloc")]
+impl<T> DefaultMonoid for Vec<T> {}
+
+#[cfg(feature = "alloc")]
+impl DefaultMonoid for String {}
+
+#[cfg(feature = "alloc")]
+impl<T> DefaultMonoid for VecDeque<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T> DefaultMonoid for LinkedList<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> DefaultMonoid for BinaryHeap<T> {}
+
+#[cfg(feature = "std")]
+impl DefaultMonoid for OsString {}
+
+#[cfg(feature = "std")]
+impl DefaultMonoid for PathBuf {}
+
+// `Duration::default` is `Duration::ZERO`
+impl DefaultMonoid for Duration {}
+
+// `Box` is a fundamental type, so implementing `Monoid` for any `Box<T>` would overlap with the
+// blanket implementation over `DefaultMonoid`.
+#[cfg(feature = "alloc")]
+impl<T: DefaultMonoid> DefaultMonoid for Box<T> {}
+
+impl<T: Monoid, E> Monoid for Result<T, E> {
+    fn empty() -> Self {
+        Ok(T::empty())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Monoid + Clone> Monoid for Rc<T> {
     fn empty() -> Self {
-        String::new()
+        Rc::new(T::empty())
     }
 }
 
-impl<T: Monoid, U: Monoid> Monoid for (T, U) {
+#[cfg(feature = "alloc")]
+impl<T: Monoid + Clone> Monoid for Arc<T> {
     fn empty() -> Self {
-        (T::empty(), U::empty())
+        Arc::new(T::empty())
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<'a, B: ?Sized + ToOwned> Monoid for Cow<'a, B>
+where
+    B::Owned: Monoid,
+    Self: Semigroup,
+{
+    fn empty() -> Self {
+        Cow::Owned(B::Owned::empty())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V: Semigroup, S: BuildHasher + Default> Monoid for HashMap<K, V, S> {
+    fn empty() -> Self {
+        HashMap::default()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V: Semigroup> Monoid for BTreeMap<K, V> {
+    fn empty() -> Self {
+        BTreeMap::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Monoid for BTreeSet<T> {
+    fn empty() -> Self {
+        BTreeSet::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher + Default> Monoid for HashSet<T, S> {
+    fn empty() -> Self {
+        HashSet::default()
+    }
+}
+
+impl<T: Monoid, const N: usize> Monoid for [T; N] {
+    fn empty() -> Self {
+        core::array::from_fn(|_| T::empty())
+    }
+}
+
+/// A monoid where every element has an inverse, such that `x.op(x.inverse()) == empty()`.
+///
+/// In this crate, `Sum<T>` is a group for any signed `T`, and `Product<T>` is a group over real
+/// numbers (`f32`, `f64`), where `inverse` is the reciprocal. Note that `Product(0.0)` has no
+/// inverse, and inverting it yields infinity.
+pub trait Group: Monoid {
+    fn inverse(self) -> Self;
+
+    /// Combines `self` with the inverse of `other`, undoing a previous `op` with `other`.
+    fn subtract(self, other: Self) -> Self {
+        self.op(other.inverse())
+    }
+}
+
+impl Group for () {
+    fn inverse(self) -> Self {}
+}
+
+impl<T: ?Sized> Group for PhantomData<T> {
+    fn inverse(self) -> Self {
+        PhantomData
+    }
+}
+
+impl<T: Group, const N: usize> Group for [T; N] {
+    fn inverse(self) -> Self {
+        self.map(Group::inverse)
+    }
+}
+
+/// A monoid whose operation commutes: `x.op(y) == y.op(x)`.
+///
+/// Bounding on this trait allows combining elements in any order, for instance in parallel or
+/// unordered reductions. Concatenation-like monoids such as `Vec` or `String` don't qualify:
+///
+/// ```compile_fail
+/// use category::traits::CommutativeMonoid;
+///
+/// fn unordered<M: CommutativeMonoid>(_: M) {}
+/// unordered(String::from("not commutative"));
+/// ```
+pub trait CommutativeMonoid: Monoid {}
+
+impl CommutativeMonoid for () {}
+
+impl<T: ?Sized> CommutativeMonoid for PhantomData<T> {}
+
+impl CommutativeMonoid for Duration {}
+
+impl<T: CommutativeMonoid, const N: usize> CommutativeMonoid for [T; N] {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> CommutativeMonoid for BTreeSet<T> {}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher + Default> CommutativeMonoid for HashSet<T, S> {}
+
+/// An idempotent semigroup, where `x.op(x) == x`.
+///
+/// Repeated elements never change the result of combining a band, which
+/// [`Band::concat_dedup`] exploits. Additive semigroups are not bands:
+///
+/// ```compile_fail
+/// use category::traits::Band;
+/// use category::Sum;
+///
+/// fn idempotent<B: Band>(_: B) {}
+/// idempotent(Sum(1));
+/// ```
+pub trait Band: Semigroup {
+    /// Returns whether `self` absorbs anything it is combined with, such as `Any(true)`. Once
+    /// reached, combining further elements cannot change the result.
+    fn is_absorbing(&self) -> bool {
+        false
+    }
+
+    /// Like [`Semigroup::concat`], but skips elements equal to the accumulated value, and stops
+    /// consuming the iterator as soon as an absorbing element has been reached.
+    fn concat_dedup<I: Iterator<Item = Self>>(
+        this: impl IntoIterator<Item = Self, IntoIter = I>,
+    ) -> Option<Self>
+    where
+        Self: PartialEq,
+    {
+        let mut res: Option<Self> = None;
+        for x in this {
+            res = Some(match res {
+                None => x,
+                Some(y) if y == x => y,
+                Some(y) => y.op(x),
+            });
+            if res.as_ref().is_some_and(Band::is_absorbing) {
+                break;
+            }
+        }
+        res
+    }
+}
+
+impl Band for () {}
+
+impl<T: ?Sized> Band for PhantomData<T> {}
+
+impl<T: Band, const N: usize> Band for [T; N] {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Band for BTreeSet<T> {}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher> Band for HashSet<T, S> {}
+
+/// A commutative band, describing either the join or the meet of a partial order.
+pub trait Semilattice: CommutativeMonoid + Band {}
+
+impl Semilattice for () {}
+
+impl<T: ?Sized> Semilattice for PhantomData<T> {}
+
+impl<T: Semilattice, const N: usize> Semilattice for [T; N] {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Semilattice for BTreeSet<T> {}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher + Default> Semilattice for HashSet<T, S> {}
+
+/// Combines the items of an iterator as a method, so that folds read left to right:
+/// `data.iter().copied().map(Sum).mconcat()`.
+pub trait MonoidIteratorExt: Iterator + Sized {
+    /// Combines the items with `Monoid::concat`.
+    fn mconcat(self) -> Self::Item
+    where
+        Self::Item: Monoid,
+    {
+        Monoid::concat(self)
+    }
+
+    /// Combines the items with `Semigroup::concat`, returning `None` if there are none.
+    fn sconcat(self) -> Option<Self::Item>
+    where
+        Self::Item: Semigroup,
+    {
+        Semigroup::concat(self)
+    }
+
+    /// Maps the items into a monoid and combines them.
+    fn fold_map<M: Monoid, F: FnMut(Self::Item) -> M>(self, f: F) -> M {
+        Monoid::concat(self.map(f))
+    }
+}
+
+impl<I: Iterator> MonoidIteratorExt for I {}
+
+/// A pair of semilattices over the same type, related by the absorption laws
+/// `a.join(a.meet(b)) == a` and `a.meet(a.join(b)) == a`.
+///
+/// Total orders are lattices where `join` is the maximum and `meet` the minimum, corresponding to
+/// the `Max` and `Min` semilattices. This is implemented for the primitive integers, `bool` and
+/// `char`; collections are left to implement it as union and intersection instead.
+pub trait Lattice: Sized {
+    fn join(self, other: Self) -> Self;
+    fn meet(self, other: Self) -> Self;
+}
+
+/// A lattice with a least element `bottom`, the identity of `join`, and a greatest element `top`,
+/// the identity of `meet`.
+pub trait BoundedLattice: Lattice {
+    fn bottom() -> Self;
+    fn top() -> Self;
+}
+
+macro_rules! impl_lattice_ord {
+    ($($t:ty: $bottom:expr, $top:expr;)*) => {
+        $(
+            impl Lattice for $t {
+                fn join(self, other: Self) -> Self {
+                    self.max(other)
+                }
+
+                fn meet(self, other: Self) -> Self {
+                    self.min(other)
+                }
+            }
+
+            impl BoundedLattice for $t {
+                fn bottom() -> Self {
+                    $bottom
+                }
+
+                fn top() -> Self {
+                    $top
+                }
+            }
+        )*
+    };
+}
+
+impl_lattice_ord! {
+    u8: u8::MIN, u8::MAX;
+    u16: u16::MIN, u16::MAX;
+    u32: u32::MIN, u32::MAX;
+    u64: u64::MIN, u64::MAX;
+    u128: u128::MIN, u128::MAX;
+    usize: usize::MIN, usize::MAX;
+    i8: i8::MIN, i8::MAX;
+    i16: i16::MIN, i16::MAX;
+    i32: i32::MIN, i32::MAX;
+    i64: i64::MIN, i64::MAX;
+    i128: i128::MIN, i128::MAX;
+    isize: isize::MIN, isize::MAX;
+    bool: false, true;
+    char: '\0', char::MAX;
+}
+
+// `OrderedFloat` orders NaN above infinity, which makes it the top
+#[cfg(feature = "ordered-float")]
+impl_lattice_ord! {
+    ordered_float::OrderedFloat<f32>: f32::NEG_INFINITY.into(), f32::NAN.into();
+    ordered_float::OrderedFloat<f64>: f64::NEG_INFINITY.into(), f64::NAN.into();
+    ordered_float::NotNan<f32>: ordered_float::NotNan::new(f32::NEG_INFINITY).unwrap(),
+        ordered_float::NotNan::new(f32::INFINITY).unwrap();
+    ordered_float::NotNan<f64>: ordered_float::NotNan::new(f64::NEG_INFINITY).unwrap(),
+        ordered_float::NotNan::new(f64::INFINITY).unwrap();
+}
+
+macro_rules! impl_tuple {
+    ($($t:ident $i:tt),+) => {
+        /// Combines tuples component-wise.
+        impl<$($t: Semigroup),+> Semigroup for ($($t,)+) {
+            fn op(self, other: Self) -> Self {
+                ($(self.$i.op(other.$i),)+)
+            }
+        }
+
+        impl<$($t: Monoid),+> Monoid for ($($t,)+) {
+            fn empty() -> Self {
+                ($(<$t as Monoid>::empty(),)+)
+            }
+        }
+
+        impl<$($t: Group),+> Group for ($($t,)+) {
+            fn inverse(self) -> Self {
+                ($(self.$i.inverse(),)+)
+            }
+        }
+
+        impl<$($t: CommutativeMonoid),+> CommutativeMonoid for ($($t,)+) {}
+
+        impl<$($t: Band),+> Band for ($($t,)+) {
+            fn is_absorbing(&self) -> bool {
+                $(self.$i.is_absorbing())&&+
+            }
+        }
+
+        impl<$($t: Semilattice),+> Semilattice for ($($t,)+) {}
+    };
+}
+
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
@@ -0,0 +1,168 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Applicative effects which can short-circuit, such as `Option` and `Result`, and that a
+/// traversal can thread through.
+pub trait Effect: Sized {
+    type Item;
+    /// What remains of a failed effect, e.g. the error of a `Result`.
+    type Residual;
+    /// The same effect, holding a value of type `U` instead.
+    type Wrap<U>: Effect<Item = U, Residual = Self::Residual>;
+
+    fn branch(self) -> Result<Self::Item, Self::Residual>;
+    fn from_output<U>(x: U) -> Self::Wrap<U>;
+    fn from_residual<U>(r: Self::Residual) -> Self::Wrap<U>;
+}
+
+impl<T> Effect for Option<T> {
+    type Item = T;
+    type Residual = ();
+    type Wrap<U> = Option<U>;
+
+    fn branch(self) -> Result<T, ()> {
+        self.ok_or(())
+    }
+
+    fn from_output<U>(x: U) -> Option<U> {
+        Some(x)
+    }
+
+    fn from_residual<U>(_: ()) -> Option<U> {
+        None
+    }
+}
+
+impl<T, E> Effect for Result<T, E> {
+    type Item = T;
+    type Residual = E;
+    type Wrap<U> = Result<U, E>;
+
+    fn branch(self) -> Result<T, E> {
+        self
+    }
+
+    fn from_output<U>(x: U) -> Result<U, E> {
+        Ok(x)
+    }
+
+    fn from_residual<U>(r: E) -> Result<U, E> {
+        Err(r)
+    }
+}
+
+/// Structures which can be mapped with an effectful function, collecting the effects in order
+/// around the resulting structure, e.g. turning a `Vec<T>` into a `Result<Vec<U>, E>`.
+///
+/// Traversals stop at the first failed effect.
+pub trait Traversable: Sized {
+    type Item;
+    /// The same structure, holding values of type `U` instead.
+    type Output<U>;
+
+    fn traverse<M: Effect, F: FnMut(Self::Item) -> M>(self, f: F)
+        -> M::Wrap<Self::Output<M::Item>>;
+
+    /// Turns a structure of effects into an effect around the structure.
+    fn sequence(self) -> <Self::Item as Effect>::Wrap<Self::Output<<Self::Item as Effect>::Item>>
+    where
+        Self::Item: Effect,
+    {
+        self.traverse(|x| x)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Traversable for Vec<T> {
+    type Item = T;
+    type Output<U> = Vec<U>;
+
+    fn traverse<M: Effect, F: FnMut(T) -> M>(self, mut f: F) -> M::Wrap<Vec<M::Item>> {
+        let mut res = Vec::with_capacity(self.len());
+        for x in self {
+            match f(x).branch() {
+                Ok(y) => res.push(y),
+                Err(r) => return M::from_residual(r),
+            }
+        }
+        M::from_output(res)
+    }
+}
+
+impl<T> Traversable for Option<T> {
+    type Item = T;
+    type Output<U> = Option<U>;
+
+    fn traverse<M: Effect, F: FnMut(T) -> M>(self, mut f: F) -> M::Wrap<Option<M::Item>> {
+        match self.map(|x| f(x).branch()) {
+            None => M::from_output(None),
+            Some(Ok(y)) => M::from_output(Some(y)),
+            Some(Err(r)) => M::from_residual(r),
+        }
+    }
+}
+
+/// Maps the value of `opt` with the fallible `f`, if there is one: `None` gives `Ok(None)`. This is
+/// `Traversable::traverse` specialized to `Option` and `Result`, which accepts a `FnOnce`.
+pub fn traverse_option<T, U, E>(
+    opt: Option<T>,
+    f: impl FnOnce(T) -> Result<U, E>,
+) -> Result<Option<U>, E> {
+    match opt {
+        Some(x) => f(x).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Turns an optional `Result` into a `Result` of an option, with `None` giving `Ok(None)`.
+pub fn sequence_option<T, E>(opt: Option<Result<T, E>>) -> Result<Option<T>, E> {
+    traverse_option(opt, |x| x)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::traversable::{sequence_option, traverse_option, Traversable};
+
+    #[test]
+    fn vec_traverse_success() {
+        let v = vec!["1", "2", "3"];
+        assert_eq!(v.traverse(|s| s.parse::<i32>()), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn vec_traverse_first_failure() {
+        let mut seen = vec![];
+        let r = vec!["1", "x", "3", "y"].traverse(|s| {
+            seen.push(s);
+            s.parse::<i32>().map_err(|_| s)
+        });
+        assert_eq!(r, Err("x"));
+        assert_eq!(seen, vec!["1", "x"]);
+    }
+
+    #[test]
+    fn vec_sequence() {
+        assert_eq!(Vec::<Result<i32, ()>>::new().sequence(), Ok(vec![]));
+        assert_eq!(vec![Some(1), Some(2)].sequence(), Some(vec![1, 2]));
+        assert_eq!(vec![Some(1), None].sequence(), None);
+    }
+
+    #[test]
+    fn option_traverse() {
+        assert_eq!(Some("4").traverse(|s| s.parse::<i32>()), Ok(Some(4)));
+        assert!(Some("four").traverse(|s| s.parse::<i32>()).is_err());
+        assert_eq!(None::<&str>.traverse(|s| s.parse::<i32>()), Ok(None));
+        assert_eq!(Some(Ok::<_, ()>(1)).sequence(), Ok(Some(1)));
+    }
+
+    #[test]
+    fn option_free_traverse() {
+        let parse = |s: &str| s.parse::<i32>().map_err(|_| s.len());
+        assert_eq!(traverse_option(None, parse), Ok(None));
+        assert_eq!(traverse_option(Some("12"), parse), Ok(Some(12)));
+        assert_eq!(traverse_option(Some("twelve"), parse), Err(6));
+        assert_eq!(sequence_option(None::<Result<i32, ()>>), Ok(None));
+        assert_eq!(sequence_option(Some(Ok::<_, ()>(1))), Ok(Some(1)));
+        assert_eq!(sequence_option(Some(Err::<i32, _>("no"))), Err("no"));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::functor::{Applicative, Functor, Semigroupal};
+use crate::traits::*;
+
+/// The result of a validation, which unlike `Result` accumulates every error that occurred
+/// instead of stopping at the first one, as long as errors form a semigroup (e.g. `Vec<String>`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Validation<T, E> {
+    Valid(T),
+    Invalid(E),
+}
+
+use Validation::{Invalid, Valid};
+
+/// Combines valid values with `T::op`, and merges the errors of invalid values in order.
+impl<T: Semigroup, E: Semigroup> Semigroup for Validation<T, E> {
+    fn op(self, other: Self) -> Self {
+        match (self, other) {
+            (Valid(a), Valid(b)) => Valid(a.op(b)),
+            (Invalid(e), Invalid(f)) => Invalid(e.op(f)),
+            (Invalid(e), _) | (_, Invalid(e)) => Invalid(e),
+        }
+    }
+}
+
+impl<T: Monoid, E: Semigroup> Monoid for Validation<T, E> {
+    fn empty() -> Self {
+        Valid(T::empty())
+    }
+}
+
+impl<T, E> Validation<T, E> {
+    pub fn from_result(res: Result<T, E>) -> Self {
+        match res {
+            Ok(x) => Valid(x),
+            Err(e) => Invalid(e),
+        }
+    }
+
+    pub fn into_result(self) -> Result<T, E> {
+        match self {
+            Valid(x) => Ok(x),
+            Invalid(e) => Err(e),
+        }
+    }
+
+    /// Pairs both valid values, or merges the errors of the invalid ones.
+    pub fn zip<U>(self, other: Validation<U, E>) -> Validation<(T, U), E>
+    where
+        E: Semigroup,
+    {
+        match (self, other) {
+            (Valid(a), Valid(b)) => Valid((a, b)),
+            (Invalid(e), Invalid(f)) => Invalid(e.op(f)),
+            (Invalid(e), _) | (_, Invalid(e)) => Invalid(e),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Validation<T, E> {
+    fn from(res: Result<T, E>) -> Self {
+        Self::from_result(res)
+    }
+}
+
+impl<T, E, B> Functor<B> for Validation<T, E> {
+    type Item = T;
+    type Wrapped = Validation<B, E>;
+
+    fn fmap<F: FnMut(T) -> B>(self, mut f: F) -> Validation<B, E> {
+        match self {
+            Valid(x) => Valid(f(x)),
+            Invalid(e) => Invalid(e),
+        }
+    }
+}
+
+/// Applying an invalid function to an invalid value merges their errors, the function's first.
+impl<T, E: Semigroup, B> Applicative<B> for Validation<T, E> {
+    type WrappedFn<F> = Validation<F, E>;
+
+    fn pure(x: T) -> Self {
+        Valid(x)
+    }
+
+    fn apply<F: FnMut(T) -> B>(self, f: Validation<F, E>) -> Validation<B, E> {
+        f.zip(self).fmap(|(mut f, x)| f(x))
+    }
+}
+
+/// Pairs both valid values, or merges the errors of the invalid ones, like `zip`.
+impl<T, E: Semigroup, B> Semigroupal<B> for Validation<T, E> {
+    type Item = T;
+    type Other = Validation<B, E>;
+    type Product = Validation<(T, B), E>;
+
+    fn product(self, other: Validation<B, E>) -> Validation<(T, B), E> {
+        self.zip(other)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::functor::{Applicative, Semigroupal};
+    use crate::traits::*;
+    use crate::validation::Validation::{self, Invalid, Valid};
+    use crate::Sum;
+
+    #[derive(Debug, PartialEq)]
+    struct Form {
+        name: String,
+        age: u32,
+    }
+
+    fn name(s: &str) -> Validation<String, Vec<String>> {
+        if s.is_empty() {
+            Invalid(vec!["name is empty".into()])
+        } else {
+            Valid(s.into())
+        }
+    }
+
+    fn age(s: &str) -> Validation<u32, Vec<String>> {
+        Validation::from_result(s.parse().map_err(|_| vec![format!("invalid age {:?}", s)]))
+    }
+
+    fn form(n: &str, a: &str) -> Validation<Form, Vec<String>> {
+        Applicative::zip_with(name(n), age(a), |name, age| Form { name, age })
+    }
+
+    #[test]
+    fn validation_valid_form() {
+        let f = Form {
+            name: "alice".into(),
+            age: 30,
+        };
+        assert_eq!(form("alice", "30"), Valid(f));
+    }
+
+    #[test]
+    fn validation_accumulates_errors() {
+        let r = form("", "thirty").into_result();
+        let errors = vec![
+            "name is empty".to_string(),
+            "invalid age \"thirty\"".to_string(),
+        ];
+        assert_eq!(r, Err(errors));
+        assert_eq!(
+            form("bob", "x"),
+            Invalid(vec!["invalid age \"x\"".to_string()])
+        );
+    }
+
+    #[test]
+    fn validation_semigroup() {
+        let v: Vec<Validation<Sum<u32>, Vec<&str>>> = vec![
+            Valid(Sum(1)),
+            Invalid(vec!["a"]),
+            Valid(Sum(2)),
+            Invalid(vec!["b"]),
+        ];
+        assert_eq!(Monoid::concat(v), Invalid(vec!["a", "b"]));
+        let v: Vec<Validation<Sum<u32>, Vec<&str>>> = vec![Valid(Sum(1)), Valid(Sum(2))];
+        assert_eq!(Monoid::concat(v), Valid(Sum(3)));
+    }
+
+    #[test]
+    fn validation_zip() {
+        let a: Validation<i32, Vec<&str>> = Valid(1);
+        assert_eq!(a.clone().zip(Valid("x")), Valid((1, "x")));
+        assert_eq!(a.zip(Invalid::<(), _>(vec!["b"])), Invalid(vec!["b"]));
+    }
+
+    #[test]
+    fn validation_product_accumulates() {
+        let a: Validation<u32, Vec<&str>> = Invalid(vec!["no name"]);
+        let b: Validation<char, Vec<&str>> = Invalid(vec!["no age"]);
+        assert_eq!(a.product(b), Invalid(vec!["no name", "no age"]));
+        assert_eq!(
+            Valid::<_, Vec<&str>>(1).product(Valid('x')),
+            Valid((1, 'x'))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use crate::functor::{Applicative, Functor, Monad};
+use crate::traits::*;
+
+/// A computation producing a value of type `A` alongside a log of type `W`, which chained
+/// computations accumulate with `W::op`, e.g. a `Vec<String>` trace of what happened.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Writer<W, A> {
+    value: A,
+    log: W,
+}
+
+impl<W, A> Writer<W, A> {
+    pub fn new(value: A, log: W) -> Self {
+        Self { value, log }
+    }
+
+    /// Returns the value and the log.
+    pub fn run(self) -> (A, W) {
+        (self.value, self.log)
+    }
+
+    pub fn map<B, F: FnOnce(A) -> B>(self, f: F) -> Writer<W, B> {
+        Writer::new(f(self.value), self.log)
+    }
+
+    /// Runs the computation `f` on the value, appending its log after the current one.
+    pub fn bind<B, F: FnOnce(A) -> Writer<W, B>>(self, f: F) -> Writer<W, B>
+    where
+        W: Semigroup,
+    {
+        let next = f(self.value);
+        Writer::new(next.value, self.log.op(next.log))
+    }
+}
+
+impl<W: Monoid, A> Writer<W, A> {
+    /// A value with an empty log.
+    pub fn value(value: A) -> Self {
+        Self::new(value, W::empty())
+    }
+}
+
+impl<W> Writer<W, ()> {
+    /// Only appends `log`, ignoring the value.
+    pub fn tell(log: W) -> Self {
+        Self::new((), log)
+    }
+}
+
+impl<W, A, B> Functor<B> for Writer<W, A> {
+    type Item = A;
+    type Wrapped = Writer<W, B>;
+
+    fn fmap<F: FnMut(A) -> B>(self, f: F) -> Writer<W, B> {
+        self.map(f)
+    }
+}
+
+/// Applying a logged function to a logged value appends the value's log after the function's.
+impl<W: Monoid, A, B> Applicative<B> for Writer<W, A> {
+    type WrappedFn<F> = Writer<W, F>;
+
+    fn pure(x: A) -> Self {
+        Self::value(x)
+    }
+
+    fn apply<F: FnMut(A) -> B>(self, f: Writer<W, F>) -> Writer<W, B> {
+        let (mut f, log) = f.run();
+        Writer::new(f(self.value), log.op(self.log))
+    }
+}
+
+impl<W: Monoid, A, B> Monad<B> for Writer<W, A> {
+    fn bind<F: FnMut(A) -> Writer<W, B>>(self, f: F) -> Writer<W, B> {
+        Writer::bind(self, f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::functor::{Applicative, Functor, Monad};
+    use crate::writer::Writer;
+
+    type Logged<A> = Writer<Vec<String>, A>;
+
+    fn half(x: u32) -> Logged<u32> {
+        Writer::tell(vec![format!("halving {}", x)]).map(|_| x / 2)
+    }
+
+    fn inc(x: u32) -> Logged<u32> {
+        Writer::new(x + 1, vec![format!("incrementing {}", x)])
+    }
+
+    #[test]
+    fn writer_bind_concatenates_logs() {
+        let (value, log) = Writer::value(10).bind(half).bind(inc).run();
+        assert_eq!(value, 6);
+        assert_eq!(log, vec!["halving 10", "incrementing 5"]);
+    }
+
+    #[test]
+    fn writer_monad_laws() {
+        let pure = <Logged<u32> as Applicative<u32>>::pure;
+        assert_eq!(Monad::bind(pure(4), half), half(4));
+        assert_eq!(Monad::bind(half(4), pure), half(4));
+        assert_eq!(
+            Monad::bind(Monad::bind(half(8), half), inc),
+            Monad::bind(half(8), |x| Monad::bind(half(x), inc))
+        );
+    }
+
+    #[test]
+    fn writer_applicative() {
+        let f = Writer::new(|x: u32| x * 3, vec![String::from("tripling")]);
+        let r = inc(1).apply(f).fmap(|x| x.to_string());
+        assert_eq!(
+            r.run(),
+            (
+                String::from("6"),
+                vec![String::from("tripling"), String::from("incrementing 1")]
+            )
+        );
+    }
+}
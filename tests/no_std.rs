@@ -0,0 +1,51 @@
+//! Exercises the surface that remains without the `std` and `alloc` features, from a `no_std`
+//! crate. The library's own unit tests always link `std`, so this is what
+//! `cargo test --no-default-features` relies on to check that nothing here needs more than `core`.
+
+#![no_std]
+
+use category::semiring::{Semiring, Tropical};
+use category::stats::{Mean, MinMax, Variance};
+use category::traits::*;
+use category::*;
+use core::cmp::Ordering;
+use core::time::Duration;
+
+#[test]
+fn core_wrappers() {
+    assert_eq!(Monoid::concat([3, 1, 2].map(Min)), Min(1));
+    assert_eq!(Monoid::concat([3, 1, 2].map(Max)), Max(3));
+    assert_eq!(Monoid::concat([3, 1, 2].map(Sum)), Sum(6));
+    assert_eq!(Monoid::concat([3, 1, 2].map(Product)), Product(6));
+    assert_eq!(Monoid::concat([false, true].map(Any)), Any(true));
+    assert_eq!(Monoid::concat([false, true].map(All)), All(false));
+    assert_eq!(First::from(1).op(First::from(2)), First::from(1));
+    assert_eq!(Last::from(1).op(Last::from(2)), Last::from(2));
+}
+
+#[test]
+fn core_instances() {
+    assert_eq!(Ordering::Equal.op(Ordering::Less), Ordering::Less);
+    assert_eq!([Sum(1), Sum(2)].op([Sum(3), Sum(4)]), [Sum(4), Sum(6)]);
+    assert_eq!((Sum(1), Max(2)).op((Sum(3), Max(0))), (Sum(4), Max(2)));
+    assert_eq!(Some(Sum(1)).op(None), Some(Sum(1)));
+    assert_eq!(
+        Duration::from_secs(1).op(Duration::from_secs(2)),
+        Duration::from_secs(3)
+    );
+    assert_eq!(Tropical(3).mul(Tropical(4)), Tropical(7));
+}
+
+#[test]
+fn core_stats() {
+    let range = Monoid::concat([3, 1, 4].map(MinMax::singleton));
+    assert_eq!(range, MinMax { min: 1, max: 4 });
+    assert_eq!(Monoid::concat([1.0, 3.0].map(Mean::new)).value(), Some(2.0));
+    assert_eq!(
+        Monoid::concat([1.0, 3.0].map(Variance::new)).variance(),
+        Some(1.0)
+    );
+}
+
+#[cfg(feature = "testing")]
+category::monoid_laws!(max_laws: Max<i32> { samples: [Max(-4), Max(0), Max(9)] });